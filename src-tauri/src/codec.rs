@@ -23,3 +23,44 @@ pub fn url_decode(input: &str) -> String {
 pub fn url_encode(input: &str) -> String {
     urlencoding::encode(input).to_string()
 }
+
+const BASE32: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// RFC 4648 Base32
+pub fn base32_encode(bytes: &[u8], padding: bool) -> String {
+    let mut output = String::new();
+    for chunk in bytes.chunks(5) {
+        let mut buf = [0u8; 5];
+        buf[..chunk.len()].copy_from_slice(chunk);
+        let n = buf.iter().fold(0u64, |n, &b| n << 8 | u64::from(b));
+        let chars = (chunk.len() * 8).div_ceil(5);
+        for i in 0..8 {
+            if i < chars {
+                output.push(BASE32[(n >> (35 - i * 5)) as usize & 0x1f] as char);
+            } else if padding {
+                output.push('=');
+            }
+        }
+    }
+    output
+}
+
+/// 忽略大小写、空白和 `=` 填充
+pub fn base32_decode(input: &str) -> Result<Vec<u8>, String> {
+    let mut bytes = vec![];
+    let (mut buf, mut bits) = (0u32, 0);
+    for c in input.chars().filter(|c| !c.is_whitespace() && *c != '=') {
+        let value = BASE32
+            .iter()
+            .position(|&b| b as char == c.to_ascii_uppercase())
+            .ok_or_else(|| format!("invalid base32 character {c:?}"))?;
+        buf = buf << 5 | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buf >> bits) as u8);
+            buf &= (1 << bits) - 1;
+        }
+    }
+    Ok(bytes)
+}
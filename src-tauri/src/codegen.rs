@@ -0,0 +1,615 @@
+use serde_json::{json, Map, Value};
+use std::collections::{HashSet, VecDeque};
+
+/// 根据 JSON 样本生成类型定义
+///
+/// `input` 可以包含多个 JSON 文档（例如多次请求的响应或 NDJSON），字段在部分样本中缺失时
+/// 视为可选，出现过 null 时视为可空。`language` 取 `rust`、`typescript`、`go`、`jsonschema`。
+#[tauri::command]
+pub fn json_codegen(
+    input: &str,
+    language: &str,
+    root_name: Option<String>,
+) -> Result<String, String> {
+    let mut root = TypeInfo::default();
+    let mut samples = 0;
+    for value in serde_json::Deserializer::from_str(input).into_iter::<Value>() {
+        let value = value.map_err(|e| format!("{e:?}"))?;
+        root.merge(&TypeInfo::infer(&value));
+        samples += 1;
+    }
+    if samples == 0 {
+        return Err("no JSON sample found".to_string());
+    }
+
+    let root_name = pascal_case(root_name.as_deref().unwrap_or("Root"));
+    match language.to_ascii_lowercase().as_str() {
+        "rust" => Ok(emit_rust(&root, &root_name)),
+        "typescript" | "ts" => Ok(emit_typescript(&root, &root_name)),
+        "go" | "golang" => Ok(emit_go(&root, &root_name)),
+        "jsonschema" | "json-schema" | "schema" => emit_json_schema(&root, &root_name),
+        other => Err(format!("unknown language: {other}")),
+    }
+}
+
+#[derive(Clone, Default)]
+enum Shape {
+    /// 只见过 null 或空数组
+    #[default]
+    Unknown,
+    Bool,
+    /// `negative`：出现过负数，`large`：出现过大于 i64::MAX 的数
+    Integer {
+        negative: bool,
+        large: bool,
+    },
+    Float,
+    String,
+    Array(Box<TypeInfo>),
+    Object(ObjectShape),
+    /// 类型冲突
+    Any,
+}
+
+#[derive(Clone, Default)]
+struct TypeInfo {
+    shape: Shape,
+    nullable: bool,
+}
+
+#[derive(Clone, Default)]
+struct ObjectShape {
+    fields: Vec<(String, Field)>,
+    samples: usize,
+}
+
+#[derive(Clone)]
+struct Field {
+    ty: TypeInfo,
+    present: usize,
+}
+
+impl TypeInfo {
+    fn infer(value: &Value) -> TypeInfo {
+        let shape = match value {
+            Value::Null => {
+                return TypeInfo {
+                    shape: Shape::Unknown,
+                    nullable: true,
+                }
+            }
+            Value::Bool(_) => Shape::Bool,
+            Value::Number(n) if n.is_i64() || n.is_u64() => Shape::Integer {
+                negative: n.as_i64().is_some_and(|n| n < 0),
+                large: !n.is_i64(),
+            },
+            Value::Number(_) => Shape::Float,
+            Value::String(_) => Shape::String,
+            Value::Array(items) => {
+                let mut item = TypeInfo::default();
+                for value in items {
+                    item.merge(&TypeInfo::infer(value));
+                }
+                Shape::Array(Box::new(item))
+            }
+            Value::Object(map) => Shape::Object(ObjectShape {
+                fields: map
+                    .iter()
+                    .map(|(k, v)| {
+                        let field = Field {
+                            ty: TypeInfo::infer(v),
+                            present: 1,
+                        };
+                        (k.to_owned(), field)
+                    })
+                    .collect(),
+                samples: 1,
+            }),
+        };
+
+        TypeInfo {
+            shape,
+            nullable: false,
+        }
+    }
+
+    fn merge(&mut self, other: &TypeInfo) {
+        self.nullable |= other.nullable;
+        self.shape = match (std::mem::take(&mut self.shape), &other.shape) {
+            (Shape::Unknown, other) => other.clone(),
+            (shape, Shape::Unknown) => shape,
+            (Shape::Bool, Shape::Bool) => Shape::Bool,
+            (Shape::String, Shape::String) => Shape::String,
+            (
+                Shape::Integer { negative, large },
+                Shape::Integer {
+                    negative: other_negative,
+                    large: other_large,
+                },
+            ) => Shape::Integer {
+                negative: negative || *other_negative,
+                large: large || *other_large,
+            },
+            (Shape::Integer { .. } | Shape::Float, Shape::Integer { .. } | Shape::Float) => {
+                Shape::Float
+            }
+            (Shape::Array(mut item), Shape::Array(other)) => {
+                item.merge(other);
+                Shape::Array(item)
+            }
+            (Shape::Object(mut object), Shape::Object(other)) => {
+                for (name, field) in &other.fields {
+                    match object.fields.iter_mut().find(|(n, _)| n == name) {
+                        Some((_, existing)) => {
+                            existing.ty.merge(&field.ty);
+                            existing.present += field.present;
+                        }
+                        None => object.fields.push((name.to_owned(), field.clone())),
+                    }
+                }
+                object.samples += other.samples;
+                Shape::Object(object)
+            }
+            _ => Shape::Any,
+        };
+    }
+}
+
+impl ObjectShape {
+    fn optional(&self, field: &Field) -> bool {
+        field.present < self.samples
+    }
+}
+
+/// 给嵌套对象分配不重复的类型名，按发现顺序输出
+#[derive(Default)]
+struct Namer<'a> {
+    queue: VecDeque<(String, &'a ObjectShape)>,
+    used: HashSet<String>,
+}
+
+impl<'a> Namer<'a> {
+    fn object(&mut self, hint: &str, object: &'a ObjectShape) -> String {
+        let mut base = pascal_case(hint);
+        // Rust 中 Self 不能作类型名
+        if base == "Self" {
+            base.push_str("Type");
+        }
+        let mut name = base.clone();
+        let mut n = 2;
+        while self.used.contains(&name) {
+            name = format!("{base}{n}");
+            n += 1;
+        }
+        self.used.insert(name.clone());
+        self.queue.push_back((name.clone(), object));
+        name
+    }
+}
+
+fn emit_rust(root: &TypeInfo, root_name: &str) -> String {
+    fn rust_type<'a>(namer: &mut Namer<'a>, ty: &'a TypeInfo, hint: &str) -> String {
+        let inner = match &ty.shape {
+            Shape::Unknown | Shape::Any => "serde_json::Value".to_string(),
+            Shape::Bool => "bool".to_string(),
+            Shape::Integer { large: false, .. } => "i64".to_string(),
+            Shape::Integer {
+                negative: false, ..
+            } => "u64".to_string(),
+            Shape::Integer { .. } => "i128".to_string(),
+            Shape::Float => "f64".to_string(),
+            Shape::String => "String".to_string(),
+            Shape::Array(item) => format!("Vec<{}>", rust_type(namer, item, &singular(hint))),
+            Shape::Object(object) => namer.object(hint, object),
+        };
+        // serde_json::Value 本身可以表示 null
+        if ty.nullable && !matches!(ty.shape, Shape::Unknown | Shape::Any) {
+            format!("Option<{inner}>")
+        } else {
+            inner
+        }
+    }
+
+    let mut namer = Namer::default();
+    namer.used.insert(root_name.to_string());
+    let mut output = String::from("use serde::{Deserialize, Serialize};\n");
+
+    match &root.shape {
+        Shape::Object(object) => {
+            namer.queue.push_back((root_name.to_string(), object));
+        }
+        _ => {
+            let ty = rust_type(&mut namer, root, root_name);
+            output.push_str(&format!("\npub type {root_name} = {ty};\n"));
+        }
+    }
+
+    while let Some((name, object)) = namer.queue.pop_front() {
+        output.push_str("\n#[derive(Debug, Clone, Serialize, Deserialize)]\n");
+        output.push_str(&format!("pub struct {name} {{\n"));
+        let mut used = HashSet::new();
+        for (key, field) in &object.fields {
+            let mut ty = rust_type(&mut namer, &field.ty, key);
+            let optional = object.optional(field);
+            if optional && !ty.starts_with("Option<") {
+                ty = format!("Option<{ty}>");
+            }
+
+            let mut ident = snake_case(key);
+            // self、super、crate、Self 不能写成原始标识符
+            if matches!(ident.as_str(), "self" | "super" | "crate" | "Self") {
+                ident.push('_');
+            } else if RUST_KEYWORDS.contains(&ident.as_str()) {
+                ident = format!("r#{ident}");
+            }
+            while !used.insert(ident.clone()) {
+                ident.push('_');
+            }
+
+            let mut serde_attrs = vec![];
+            if ident.trim_start_matches("r#") != key {
+                serde_attrs.push(format!("rename = {key:?}"));
+            }
+            if optional {
+                serde_attrs.push("default, skip_serializing_if = \"Option::is_none\"".to_string());
+            }
+            if !serde_attrs.is_empty() {
+                output.push_str(&format!("    #[serde({})]\n", serde_attrs.join(", ")));
+            }
+            output.push_str(&format!("    pub {ident}: {ty},\n"));
+        }
+        output.push_str("}\n");
+    }
+
+    output
+}
+
+fn emit_typescript(root: &TypeInfo, root_name: &str) -> String {
+    fn ts_type<'a>(namer: &mut Namer<'a>, ty: &'a TypeInfo, hint: &str) -> String {
+        let inner = match &ty.shape {
+            Shape::Unknown | Shape::Any => "unknown".to_string(),
+            Shape::Bool => "boolean".to_string(),
+            Shape::Integer { .. } | Shape::Float => "number".to_string(),
+            Shape::String => "string".to_string(),
+            Shape::Array(item) => {
+                let item = ts_type(namer, item, &singular(hint));
+                if item.contains(' ') {
+                    format!("({item})[]")
+                } else {
+                    format!("{item}[]")
+                }
+            }
+            Shape::Object(object) => namer.object(hint, object),
+        };
+        if ty.nullable && !matches!(ty.shape, Shape::Unknown | Shape::Any) {
+            format!("{inner} | null")
+        } else {
+            inner
+        }
+    }
+
+    let mut namer = Namer::default();
+    namer.used.insert(root_name.to_string());
+    let mut output = String::new();
+
+    match &root.shape {
+        Shape::Object(object) => {
+            namer.queue.push_back((root_name.to_string(), object));
+        }
+        _ => {
+            let ty = ts_type(&mut namer, root, root_name);
+            output.push_str(&format!("export type {root_name} = {ty};\n"));
+        }
+    }
+
+    while let Some((name, object)) = namer.queue.pop_front() {
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        output.push_str(&format!("export interface {name} {{\n"));
+        for (key, field) in &object.fields {
+            let ty = ts_type(&mut namer, &field.ty, key);
+            let valid = key.chars().enumerate().all(|(n, c)| {
+                c == '_' || c == '$' || c.is_ascii_alphabetic() || (n > 0 && c.is_ascii_digit())
+            });
+            let key_text = if valid && !key.is_empty() {
+                key.to_owned()
+            } else {
+                serde_json::to_string(key).unwrap_or_default()
+            };
+            let optional = if object.optional(field) { "?" } else { "" };
+            output.push_str(&format!("  {key_text}{optional}: {ty};\n"));
+        }
+        output.push_str("}\n");
+    }
+
+    output
+}
+
+fn emit_go(root: &TypeInfo, root_name: &str) -> String {
+    fn go_type<'a>(namer: &mut Namer<'a>, ty: &'a TypeInfo, hint: &str, optional: bool) -> String {
+        let inner = match &ty.shape {
+            Shape::Unknown | Shape::Any => return "interface{}".to_string(),
+            Shape::Bool => "bool".to_string(),
+            Shape::Integer { large: false, .. } => "int64".to_string(),
+            Shape::Integer {
+                negative: false, ..
+            } => "uint64".to_string(),
+            // 超出 int64 和 uint64 的范围
+            Shape::Integer { .. } => "float64".to_string(),
+            Shape::Float => "float64".to_string(),
+            Shape::String => "string".to_string(),
+            // 切片本身可以为 nil
+            Shape::Array(item) => {
+                return format!("[]{}", go_type(namer, item, &singular(hint), false))
+            }
+            Shape::Object(object) => namer.object(hint, object),
+        };
+        if ty.nullable || optional {
+            format!("*{inner}")
+        } else {
+            inner
+        }
+    }
+
+    let mut namer = Namer::default();
+    namer.used.insert(root_name.to_string());
+    let mut output = String::new();
+
+    match &root.shape {
+        Shape::Object(object) => {
+            namer.queue.push_back((root_name.to_string(), object));
+        }
+        _ => {
+            let ty = go_type(&mut namer, root, root_name, false);
+            output.push_str(&format!("type {root_name} {ty}\n"));
+        }
+    }
+
+    while let Some((name, object)) = namer.queue.pop_front() {
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        output.push_str(&format!("type {name} struct {{\n"));
+        let mut used = HashSet::new();
+        let mut rows = vec![];
+        for (key, field) in &object.fields {
+            let optional = object.optional(field);
+            let ty = go_type(&mut namer, &field.ty, key, optional);
+
+            let mut ident = go_name(key);
+            while !used.insert(ident.clone()) {
+                ident.push('_');
+            }
+            let omitempty = if optional { ",omitempty" } else { "" };
+            rows.push((ident, ty, go_tag(key, omitempty)));
+        }
+        // 与 gofmt 一样对齐字段名和类型
+        let ident_width = rows.iter().map(|r| r.0.len()).max().unwrap_or(0);
+        let type_width = rows.iter().map(|r| r.1.len()).max().unwrap_or(0);
+        for (ident, ty, tag) in rows {
+            output.push_str(&format!("\t{ident:ident_width$} {ty:type_width$} {tag}\n"));
+        }
+        output.push_str("}\n");
+    }
+
+    output
+}
+
+fn emit_json_schema(root: &TypeInfo, root_name: &str) -> Result<String, String> {
+    fn schema<'a>(namer: &mut Namer<'a>, ty: &'a TypeInfo, hint: &str) -> Value {
+        let type_name = match &ty.shape {
+            Shape::Unknown | Shape::Any => return json!({}),
+            Shape::Bool => "boolean",
+            Shape::Integer { .. } => "integer",
+            Shape::Float => "number",
+            Shape::String => "string",
+            Shape::Array(item) => {
+                let items = schema(namer, item, &singular(hint));
+                let kind = if ty.nullable {
+                    json!(["array", "null"])
+                } else {
+                    json!("array")
+                };
+                return json!({ "type": kind, "items": items });
+            }
+            Shape::Object(object) => {
+                let name = namer.object(hint, object);
+                let reference = json!({ "$ref": format!("#/$defs/{name}") });
+                return if ty.nullable {
+                    json!({ "anyOf": [reference, { "type": "null" }] })
+                } else {
+                    reference
+                };
+            }
+        };
+        if ty.nullable {
+            json!({ "type": [type_name, "null"] })
+        } else {
+            json!({ "type": type_name })
+        }
+    }
+
+    fn object_schema<'a>(namer: &mut Namer<'a>, object: &'a ObjectShape) -> Value {
+        let mut properties = Map::new();
+        let mut required = vec![];
+        for (key, field) in &object.fields {
+            properties.insert(key.to_owned(), schema(namer, &field.ty, key));
+            if !object.optional(field) {
+                required.push(Value::String(key.to_owned()));
+            }
+        }
+        json!({ "type": "object", "properties": properties, "required": required })
+    }
+
+    let mut namer = Namer::default();
+    namer.used.insert(root_name.to_string());
+
+    let mut document = match &root.shape {
+        Shape::Object(object) => object_schema(&mut namer, object),
+        _ => schema(&mut namer, root, root_name),
+    };
+
+    let mut defs = Map::new();
+    while let Some((name, object)) = namer.queue.pop_front() {
+        defs.insert(name, object_schema(&mut namer, object));
+    }
+
+    if let Value::Object(map) = &mut document {
+        map.insert(
+            "$schema".to_string(),
+            json!("https://json-schema.org/draft/2020-12/schema"),
+        );
+        map.insert("title".to_string(), json!(root_name));
+        if !defs.is_empty() {
+            map.insert("$defs".to_string(), Value::Object(defs));
+        }
+    }
+
+    serde_json::to_string_pretty(&document).map_err(|e| format!("{e:?}"))
+}
+
+const RUST_KEYWORDS: [&str; 38] = [
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use",
+    "where", "while", "yield",
+];
+
+/// Go 习惯全大写的缩写
+const GO_INITIALISMS: [&str; 16] = [
+    "API", "DNS", "HTML", "HTTP", "HTTPS", "ID", "IP", "JSON", "SQL", "SSH", "TCP", "TLS", "UDP",
+    "URI", "URL", "UUID",
+];
+
+/// 按大小写变化、非字母数字字符切分单词
+fn split_words(s: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut current = String::new();
+    let chars: Vec<char> = s.chars().collect();
+    for (n, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        let boundary = c.is_uppercase()
+            && n > 0
+            && (chars[n - 1].is_lowercase()
+                || chars[n - 1].is_ascii_digit()
+                || (chars[n - 1].is_uppercase()
+                    && chars.get(n + 1).is_some_and(|next| next.is_lowercase())));
+        if boundary && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        current.push(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn pascal_case(s: &str) -> String {
+    let name: String = split_words(s)
+        .iter()
+        .map(|w| {
+            let mut chars = w.chars();
+            match chars.next() {
+                Some(first) => first
+                    .to_uppercase()
+                    .chain(chars.flat_map(|c| c.to_lowercase()))
+                    .collect(),
+                None => String::new(),
+            }
+        })
+        .collect();
+    match name.chars().next() {
+        None => "Type".to_string(),
+        Some(c) if c.is_ascii_digit() => format!("T{name}"),
+        _ => name,
+    }
+}
+
+fn snake_case(s: &str) -> String {
+    let name = split_words(s)
+        .iter()
+        .map(|w| w.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("_");
+    match name.chars().next() {
+        None => "field".to_string(),
+        Some(c) if c.is_ascii_digit() => format!("_{name}"),
+        _ => name,
+    }
+}
+
+fn go_name(s: &str) -> String {
+    let name: String = split_words(s)
+        .iter()
+        .map(|w| {
+            let upper = w.to_uppercase();
+            if GO_INITIALISMS.contains(&upper.as_str()) {
+                upper
+            } else {
+                pascal_case(w)
+            }
+        })
+        .collect();
+    match name.chars().next() {
+        None => "Field".to_string(),
+        Some(c) if c.is_ascii_digit() => format!("F{name}"),
+        _ => name,
+    }
+}
+
+/// Go 结构体标签，键名中的 `"`、`\` 转义，含反引号时整个标签改用双引号字符串
+///
+/// encoding/json 不接受含引号、反斜杠、逗号等字符的键名，此时追加注释提示。
+fn go_tag(key: &str, omitempty: &str) -> String {
+    let escape = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+    let tag = format!("json:\"{}{omitempty}\"", escape(key));
+    let mut output = if tag.contains('`') {
+        format!("\"{}\"", escape(&tag))
+    } else {
+        format!("`{tag}`")
+    };
+    let valid = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_alphanumeric() || "!#$%&()*+-./:;<=>?@[]^_{|}~ ".contains(c));
+    if !valid {
+        output.push_str(" // encoding/json ignores this key name");
+    }
+    output
+}
+
+/// 数组元素的类型名：items -> Item，categories -> Category
+fn singular(s: &str) -> String {
+    if let Some(stem) = s.strip_suffix("ies") {
+        format!("{stem}y")
+    } else if s.ends_with("ss") || s.ends_with("us") {
+        format!("{s}Item")
+    } else if let Some(stem) = s.strip_suffix('s').filter(|stem| !stem.is_empty()) {
+        stem.to_string()
+    } else {
+        format!("{s}Item")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typescript_keeps_sample_order_and_quotes_keys_as_json() {
+        let input =
+            "{\"zeta\": 1, \"a-b\": \"x\", \"\\u0007\": true}\n{\"zeta\": 2, \"alpha\": null}";
+        let output = json_codegen(input, "typescript", None).unwrap();
+        assert_eq!(
+            output,
+            "export interface Root {\n  zeta: number;\n  \"a-b\"?: string;\n  \"\\u0007\"?: boolean;\n  alpha?: unknown;\n}\n"
+        );
+    }
+}
@@ -0,0 +1,274 @@
+use serde::{Deserialize, Serialize};
+use similar::{Algorithm, ChangeTag, DiffTag, TextDiff};
+
+use crate::format;
+
+#[derive(Serialize, Debug)]
+pub struct DiffResult {
+    pub equal: bool,
+    pub insertions: usize,
+    pub deletions: usize,
+    pub unified: String,
+    pub hunks: Vec<Hunk>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Hunk {
+    pub old_start: usize,
+    pub old_len: usize,
+    pub new_start: usize,
+    pub new_len: usize,
+    pub lines: Vec<DiffLine>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct DiffLine {
+    pub tag: &'static str,
+    pub old_line: Option<usize>,
+    pub new_line: Option<usize>,
+    pub text: String,
+    /// 行内变化，只有成对的删除/插入行才有
+    pub spans: Vec<InlineSpan>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct InlineSpan {
+    pub changed: bool,
+    pub text: String,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct DiffOptions {
+    pub ignore_whitespace: bool,
+    pub ignore_case: bool,
+    pub ignore_line_endings: bool,
+    /// `json` 或 `xml` 时先用 `json_format`/`xml_format` 统一格式
+    pub normalize: Option<String>,
+    /// `word`（默认）、`char`、`none`，成对修改行的行内对比粒度
+    pub inline: Option<String>,
+    /// 上下文行数，默认 3
+    pub context: Option<usize>,
+}
+
+/// 文本对比，返回 unified diff 以及按行、行内的变化
+#[tauri::command]
+pub fn text_diff(old: &str, new: &str, options: Option<DiffOptions>) -> Result<DiffResult, String> {
+    let options = options.unwrap_or_default();
+    let (old, new) = match options.normalize.as_deref().unwrap_or("") {
+        "" | "none" => (old.to_string(), new.to_string()),
+        "json" => (format::json_format(old)?, format::json_format(new)?),
+        "xml" => (
+            format::xml_format(old, None, None, None)?,
+            format::xml_format(new, None, None, None)?,
+        ),
+        other => return Err(format!("unknown normalization: {other}")),
+    };
+    let inline = match options.inline.as_deref().unwrap_or("word") {
+        "word" => Some(false),
+        "char" => Some(true),
+        "none" => None,
+        other => return Err(format!("unknown inline mode: {other}")),
+    };
+
+    let old_lines = split_lines(&old);
+    let new_lines = split_lines(&new);
+
+    // 比较用的键，忽略选项只影响键，输出保留原文
+    let key = |line: &str| {
+        let mut line = line.to_string();
+        if options.ignore_line_endings {
+            line = line.trim_end_matches(['\r', '\n']).to_string();
+        }
+        if options.ignore_whitespace {
+            line.retain(|c| !c.is_whitespace());
+        }
+        if options.ignore_case {
+            line = line.to_lowercase();
+        }
+        line
+    };
+    let old_keys = old_lines.iter().map(|l| key(l)).collect::<Vec<_>>();
+    let new_keys = new_lines.iter().map(|l| key(l)).collect::<Vec<_>>();
+
+    let ops = similar::capture_diff_slices(Algorithm::Myers, &old_keys, &new_keys);
+    let mut insertions = 0;
+    let mut deletions = 0;
+    for op in &ops {
+        let (tag, old_range, new_range) = op.as_tag_tuple();
+        if tag != DiffTag::Equal {
+            deletions += old_range.len();
+            insertions += new_range.len();
+        }
+    }
+
+    let mut hunks = vec![];
+    for group in similar::group_diff_ops(ops, options.context.unwrap_or(3)) {
+        let mut lines = vec![];
+        for op in &group {
+            let (tag, old_range, new_range) = op.as_tag_tuple();
+            match tag {
+                DiffTag::Equal => {
+                    for (o, n) in old_range.zip(new_range) {
+                        lines.push(DiffLine {
+                            tag: "equal",
+                            old_line: Some(o + 1),
+                            new_line: Some(n + 1),
+                            text: new_lines[n].to_string(),
+                            spans: vec![],
+                        });
+                    }
+                }
+                _ => {
+                    let paired = old_range.len().min(new_range.len());
+                    let mut inserted = vec![];
+                    for (i, o) in old_range.clone().enumerate() {
+                        let mut deleted = DiffLine {
+                            tag: "delete",
+                            old_line: Some(o + 1),
+                            new_line: None,
+                            text: old_lines[o].to_string(),
+                            spans: vec![],
+                        };
+                        let mut insert = None;
+                        if i < paired {
+                            let n = new_range.start + i;
+                            let mut line = DiffLine {
+                                tag: "insert",
+                                old_line: None,
+                                new_line: Some(n + 1),
+                                text: new_lines[n].to_string(),
+                                spans: vec![],
+                            };
+                            if let Some(chars) = inline {
+                                (deleted.spans, line.spans) =
+                                    inline_spans(old_lines[o], new_lines[n], chars);
+                            }
+                            insert = Some(line);
+                        }
+                        lines.push(deleted);
+                        inserted.extend(insert);
+                    }
+                    for n in new_range.skip(paired) {
+                        inserted.push(DiffLine {
+                            tag: "insert",
+                            old_line: None,
+                            new_line: Some(n + 1),
+                            text: new_lines[n].to_string(),
+                            spans: vec![],
+                        });
+                    }
+                    lines.extend(inserted);
+                }
+            }
+        }
+
+        let (old_start, old_len, new_start, new_len) = match (group.first(), group.last()) {
+            (Some(first), Some(last)) => {
+                let (old_start, new_start) = (first.old_range().start, first.new_range().start);
+                let old_len = last.old_range().end - old_start;
+                let new_len = last.new_range().end - new_start;
+                (old_start + 1, old_len, new_start + 1, new_len)
+            }
+            _ => continue,
+        };
+        hunks.push(Hunk {
+            old_start,
+            old_len,
+            new_start,
+            new_len,
+            lines,
+        });
+    }
+
+    Ok(DiffResult {
+        equal: hunks.is_empty(),
+        insertions,
+        deletions,
+        unified: unified_text(&hunks),
+        hunks,
+    })
+}
+
+/// 按行切分，保留行尾（含 `\r`），以便区分换行符差异
+fn split_lines(s: &str) -> Vec<&str> {
+    s.split_inclusive('\n').collect()
+}
+
+fn inline_spans(old: &str, new: &str, chars: bool) -> (Vec<InlineSpan>, Vec<InlineSpan>) {
+    let old = old.trim_end_matches(['\r', '\n']);
+    let new = new.trim_end_matches(['\r', '\n']);
+    let diff = if chars {
+        TextDiff::configure()
+            .algorithm(Algorithm::Myers)
+            .diff_chars(old, new)
+    } else {
+        TextDiff::configure()
+            .algorithm(Algorithm::Myers)
+            .diff_words(old, new)
+    };
+
+    let mut old_spans: Vec<InlineSpan> = vec![];
+    let mut new_spans: Vec<InlineSpan> = vec![];
+    for change in diff.iter_all_changes() {
+        let (spans, changed) = match change.tag() {
+            ChangeTag::Equal => {
+                push_span(&mut old_spans, false, change.value());
+                (&mut new_spans, false)
+            }
+            ChangeTag::Delete => (&mut old_spans, true),
+            ChangeTag::Insert => (&mut new_spans, true),
+        };
+        push_span(spans, changed, change.value());
+    }
+
+    (old_spans, new_spans)
+}
+
+/// 相邻同类片段合并
+fn push_span(spans: &mut Vec<InlineSpan>, changed: bool, text: &str) {
+    match spans.last_mut() {
+        Some(last) if last.changed == changed => last.text.push_str(text),
+        _ => spans.push(InlineSpan {
+            changed,
+            text: text.to_string(),
+        }),
+    }
+}
+
+fn unified_text(hunks: &[Hunk]) -> String {
+    if hunks.is_empty() {
+        return String::new();
+    }
+
+    let mut output = String::from("--- a\n+++ b\n");
+    for hunk in hunks {
+        output.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            hunk_range(hunk.old_start, hunk.old_len),
+            hunk_range(hunk.new_start, hunk.new_len)
+        ));
+        for line in &hunk.lines {
+            let sign = match line.tag {
+                "delete" => '-',
+                "insert" => '+',
+                _ => ' ',
+            };
+            output.push(sign);
+            output.push_str(&line.text);
+            if !line.text.ends_with('\n') {
+                output.push_str("\n\\ No newline at end of file\n");
+            }
+        }
+    }
+    output
+}
+
+fn hunk_range(start: usize, len: usize) -> String {
+    match len {
+        // 空范围按惯例指向前一行
+        0 => format!("{},0", start - 1),
+        1 => format!("{start}"),
+        _ => format!("{start},{len}"),
+    }
+}
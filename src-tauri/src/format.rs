@@ -1,4 +1,13 @@
-use xml::{EmitterConfig, ParserConfig};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use xml::attribute::OwnedAttribute;
+use xml::common::Position;
+use xml::name::OwnedName;
+use xml::namespace::{NS_XMLNS_PREFIX, NS_XML_PREFIX};
+use xml::reader::{self, XmlEvent};
+use xml::ParserConfig;
+
+const XML_NAMESPACE: &str = "http://www.w3.org/XML/1998/namespace";
 
 #[tauri::command]
 pub fn json_format(input: &str) -> Result<String, String> {
@@ -11,26 +20,725 @@ pub fn json_format(input: &str) -> Result<String, String> {
     }
 }
 
+/// 格式化 XML
+///
+/// `minify` 为 true 时输出单行；`indent` 为缩进空格数（默认 2）；
+/// `wrap_attributes` 为行宽上限，超出时每个属性单独一行（0 表示多属性元素总是换行）。
 #[tauri::command]
-pub fn xml_format(input: &str) -> Result<String, String> {
-    let mut output = vec![];
-
+pub fn xml_format(
+    input: &str,
+    minify: Option<bool>,
+    indent: Option<usize>,
+    wrap_attributes: Option<usize>,
+) -> Result<String, String> {
     let reader = ParserConfig::new()
         .trim_whitespace(true)
         .ignore_comments(false)
         .create_reader(input.as_bytes());
 
-    let mut writer = EmitterConfig::new()
-        .perform_indent(true)
-        .normalize_empty_elements(false)
-        .create_writer(&mut output);
+    let mut declaration = None;
+    let mut nodes: Vec<XmlNode> = vec![];
+    let mut stack: Vec<(XmlElement, BTreeMap<String, String>)> = vec![];
 
     for event in reader {
-        if let Some(event) = event.map_err(|e| format!("{e:?}"))?.as_writer_event() {
-            writer.write(event).map_err(|e| format!("{e:?}"))?
+        let node = match event.map_err(|e| xml_error(&e))? {
+            XmlEvent::StartDocument {
+                version,
+                encoding,
+                standalone,
+            } => {
+                declaration = Some((version.to_string(), encoding, standalone));
+                continue;
+            }
+            XmlEvent::StartElement {
+                name,
+                attributes,
+                namespace,
+            } => {
+                let scope = in_scope_namespaces(&namespace.0);
+                let parent = stack.last().map(|(_, scope)| scope);
+
+                let mut prefixes = scope.keys().collect::<BTreeSet<_>>();
+                prefixes.extend(parent.into_iter().flat_map(|p| p.keys()));
+
+                let mut attrs = vec![];
+                for prefix in prefixes {
+                    let uri = scope.get(prefix).map_or("", |s| s.as_str());
+                    if parent
+                        .and_then(|p| p.get(prefix))
+                        .map_or("", |s| s.as_str())
+                        != uri
+                    {
+                        attrs.push((xmlns_name(prefix), uri.to_owned()));
+                    }
+                }
+                for attr in &attributes {
+                    attrs.push((qualified_name(&attr.name), attr.value.to_owned()));
+                }
+
+                let element = XmlElement {
+                    name: qualified_name(&name),
+                    attributes: attrs,
+                    children: vec![],
+                };
+                stack.push((element, scope));
+                continue;
+            }
+            XmlEvent::EndElement { .. } => match stack.pop() {
+                Some((element, _)) => XmlNode::Element(element),
+                None => continue,
+            },
+            XmlEvent::Characters(s) => XmlNode::Text(s),
+            XmlEvent::CData(s) => XmlNode::CData(s),
+            XmlEvent::Comment(s) => XmlNode::Comment(s),
+            XmlEvent::ProcessingInstruction { name, data } => XmlNode::Pi(name, data),
+            _ => continue,
+        };
+
+        match stack.last_mut() {
+            Some((parent, _)) => parent.children.push(node),
+            None => nodes.push(node),
         }
     }
 
-    Ok(String::from_utf8_lossy(&output).to_string())
+    let style = XmlStyle {
+        pretty: !minify.unwrap_or(false),
+        indent: " ".repeat(indent.unwrap_or(2)),
+        wrap_attributes,
+    };
+
+    let mut output = String::new();
+    if let Some((version, encoding, standalone)) = declaration {
+        write!(
+            output,
+            "<?xml version=\"{version}\" encoding=\"{encoding}\""
+        )
+        .map_err(|e| e.to_string())?;
+        if let Some(standalone) = standalone {
+            let standalone = if standalone { "yes" } else { "no" };
+            write!(output, " standalone=\"{standalone}\"").map_err(|e| e.to_string())?;
+        }
+        output.push_str("?>");
+    }
+    for node in &nodes {
+        if style.pretty && !output.is_empty() {
+            output.push('\n');
+        }
+        style.write_node(&mut output, node, 0);
+    }
+
+    Ok(output)
 }
 
+/// XML 规范化（C14N），用于核对 XML-DSig 摘要
+///
+/// `algorithm` 可以是 `c14n`、`c14n11`、`exc-c14n`，也可以直接粘贴签名中的算法 URI
+/// （含 `#WithComments` 后缀）。`inclusive_prefixes` 对应 Exclusive C14N 的 PrefixList，
+/// 以空白分隔，`#default` 表示默认命名空间。`subset_id` 指定只规范化 Id/ID/id 属性
+/// 等于该值的元素子树，对应签名 Reference 的 `URI="#..."`。
+/// C14N 1.1 对子集只继承 xml:lang 和 xml:space，不做 xml:base 修正。
+#[tauri::command]
+pub fn xml_canonicalize(
+    input: &str,
+    algorithm: &str,
+    with_comments: Option<bool>,
+    inclusive_prefixes: Option<String>,
+    subset_id: Option<String>,
+) -> Result<String, String> {
+    let (method, comments) = parse_c14n_algorithm(algorithm)?;
+    let with_comments = comments || with_comments.unwrap_or(false);
+    let inclusive_prefixes = inclusive_prefixes
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(|p| if p == "#default" { "" } else { p }.to_string())
+        .collect::<Vec<_>>();
+    let subset_id = subset_id
+        .map(|id| id.trim().trim_start_matches('#').to_string())
+        .filter(|id| !id.is_empty());
+
+    let reader = ParserConfig::new()
+        .trim_whitespace(false)
+        .whitespace_to_characters(true)
+        .cdata_to_characters(true)
+        .coalesce_characters(true)
+        .ignore_comments(!with_comments)
+        .create_reader(input.as_bytes());
+
+    let mut output = String::new();
+    let mut depth = 0;
+    let mut apex: Option<usize> = None;
+    let mut done = false;
+    let mut root_seen = false;
+    let mut rendered: Vec<BTreeMap<String, String>> = vec![];
+    let mut xml_scope: Vec<BTreeMap<String, String>> = vec![];
+
+    for event in reader {
+        let event = event.map_err(|e| xml_error(&e))?;
+        if done {
+            continue;
+        }
+
+        match event {
+            XmlEvent::StartElement {
+                name,
+                attributes,
+                namespace,
+            } => {
+                depth += 1;
+
+                let mut inherited = xml_scope.last().cloned().unwrap_or_default();
+                for attr in &attributes {
+                    if attr.name.namespace.as_deref() == Some(XML_NAMESPACE) {
+                        inherited.insert(attr.name.local_name.to_owned(), attr.value.to_owned());
+                    }
+                }
+                xml_scope.push(inherited);
+
+                if apex.is_none() {
+                    let selected = match &subset_id {
+                        Some(id) => attributes.iter().any(|attr| {
+                            matches!(attr.name.local_name.as_str(), "Id" | "ID" | "id")
+                                && &attr.value == id
+                        }),
+                        None => true,
+                    };
+                    if !selected {
+                        continue;
+                    }
+                    apex = Some(depth);
+                    root_seen = true;
+                }
+
+                let scope = in_scope_namespaces(&namespace.0);
+                let parent = rendered.last().cloned().unwrap_or_default();
+                let rendered_uri = |prefix: &str| parent.get(prefix).map_or("", |s| s.as_str());
+
+                let candidates: Vec<String> = match method {
+                    C14nMethod::Exclusive => {
+                        let mut utilized = vec![name.prefix.clone().unwrap_or_default()];
+                        for attr in &attributes {
+                            if let Some(prefix) = &attr.name.prefix {
+                                utilized.push(prefix.to_owned());
+                            }
+                        }
+                        utilized.extend(
+                            inclusive_prefixes
+                                .iter()
+                                .filter(|p| p.is_empty() || scope.contains_key(*p))
+                                .cloned(),
+                        );
+                        utilized
+                    }
+                    _ => std::iter::once(String::new())
+                        .chain(scope.keys().cloned())
+                        .collect(),
+                };
+
+                let mut declarations = BTreeMap::new();
+                for prefix in candidates {
+                    if prefix == NS_XML_PREFIX {
+                        continue;
+                    }
+                    let uri = scope.get(&prefix).map_or("", |s| s.as_str());
+                    if uri != rendered_uri(&prefix) {
+                        declarations.insert(prefix, uri.to_string());
+                    }
+                }
+
+                let mut attrs: Vec<&OwnedAttribute> = attributes.iter().collect();
+                let inheritable: &[&str] = match method {
+                    C14nMethod::Inclusive10 => &["base", "id", "lang", "space"],
+                    C14nMethod::Inclusive11 => &["lang", "space"],
+                    C14nMethod::Exclusive => &[],
+                };
+                let mut inherited_attrs = vec![];
+                if apex == Some(depth) && depth > 1 {
+                    let ancestors = &xml_scope[xml_scope.len() - 2];
+                    for (local_name, value) in ancestors {
+                        let present = attributes.iter().any(|attr| {
+                            attr.name.namespace.as_deref() == Some(XML_NAMESPACE)
+                                && &attr.name.local_name == local_name
+                        });
+                        if inheritable.contains(&local_name.as_str()) && !present {
+                            inherited_attrs.push(OwnedAttribute::new(
+                                OwnedName::qualified(
+                                    local_name.as_str(),
+                                    XML_NAMESPACE,
+                                    Some(NS_XML_PREFIX),
+                                ),
+                                value.as_str(),
+                            ));
+                        }
+                    }
+                }
+                attrs.extend(inherited_attrs.iter());
+                attrs.sort_by(|a, b| {
+                    (
+                        a.name.namespace.as_deref().unwrap_or(""),
+                        &a.name.local_name,
+                    )
+                        .cmp(&(
+                            b.name.namespace.as_deref().unwrap_or(""),
+                            &b.name.local_name,
+                        ))
+                });
+
+                output.push('<');
+                output.push_str(&qualified_name(&name));
+                for (prefix, uri) in &declarations {
+                    output.push(' ');
+                    output.push_str(&xmlns_name(prefix));
+                    output.push_str("=\"");
+                    output.push_str(&c14n_escape_attr(uri));
+                    output.push('"');
+                }
+                for attr in attrs {
+                    output.push(' ');
+                    output.push_str(&qualified_name(&attr.name));
+                    output.push_str("=\"");
+                    output.push_str(&c14n_escape_attr(&attr.value));
+                    output.push('"');
+                }
+                output.push('>');
+
+                let mut current = parent;
+                current.extend(declarations);
+                rendered.push(current);
+            }
+            XmlEvent::EndElement { name } => {
+                if apex.is_some() {
+                    output.push_str("</");
+                    output.push_str(&qualified_name(&name));
+                    output.push('>');
+                    rendered.pop();
+
+                    if apex == Some(depth) {
+                        apex = None;
+                        done = subset_id.is_some();
+                    }
+                }
+                xml_scope.pop();
+                depth -= 1;
+            }
+            XmlEvent::Characters(s) if apex.is_some() => {
+                output.push_str(&c14n_escape_text(&s));
+            }
+            XmlEvent::Comment(s) => {
+                let node = format!("<!--{s}-->");
+                c14n_push_node(
+                    &mut output,
+                    node,
+                    apex.is_some(),
+                    depth,
+                    root_seen,
+                    &subset_id,
+                );
+            }
+            XmlEvent::ProcessingInstruction { name, data } => {
+                // 目标名和数据之间只保留一个空格
+                let node = match data.as_deref().map(str::trim_start) {
+                    Some(data) if !data.is_empty() => format!("<?{name} {data}?>"),
+                    _ => format!("<?{name}?>"),
+                };
+                c14n_push_node(
+                    &mut output,
+                    node,
+                    apex.is_some(),
+                    depth,
+                    root_seen,
+                    &subset_id,
+                );
+            }
+            _ => {}
+        }
+    }
+
+    if let (Some(id), false) = (&subset_id, done) {
+        return Err(format!("no element with Id \"{id}\" found"));
+    }
+
+    Ok(output)
+}
+
+#[derive(Clone, Copy)]
+enum C14nMethod {
+    Inclusive10,
+    Inclusive11,
+    Exclusive,
+}
+
+fn parse_c14n_algorithm(algorithm: &str) -> Result<(C14nMethod, bool), String> {
+    let algorithm = algorithm.trim();
+    let (base, with_comments) = match algorithm.strip_suffix("WithComments") {
+        Some(base) => (base.trim_end_matches(['#', '-']), true),
+        None => (algorithm.trim_end_matches('#'), false),
+    };
+
+    let method = match base.to_ascii_lowercase().as_str() {
+        "c14n" | "c14n10" | "http://www.w3.org/tr/2001/rec-xml-c14n-20010315" => {
+            C14nMethod::Inclusive10
+        }
+        "c14n11" | "http://www.w3.org/2006/12/xml-c14n11" => C14nMethod::Inclusive11,
+        "exc-c14n" | "http://www.w3.org/2001/10/xml-exc-c14n" => C14nMethod::Exclusive,
+        _ => return Err(format!("unknown canonicalization algorithm: {algorithm}")),
+    };
+
+    Ok((method, with_comments))
+}
+
+/// 文档元素之外的注释和处理指令：根元素之前的后接换行，之后的前置换行
+fn c14n_push_node(
+    output: &mut String,
+    node: String,
+    in_output: bool,
+    depth: usize,
+    root_seen: bool,
+    subset_id: &Option<String>,
+) {
+    if in_output {
+        output.push_str(&node);
+    } else if depth == 0 && subset_id.is_none() {
+        if root_seen {
+            output.push('\n');
+            output.push_str(&node);
+        } else {
+            output.push_str(&node);
+            output.push('\n');
+        }
+    }
+}
+
+fn c14n_escape_text(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '\r' => escaped.push_str("&#xD;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn c14n_escape_attr(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\t' => escaped.push_str("&#x9;"),
+            '\n' => escaped.push_str("&#xA;"),
+            '\r' => escaped.push_str("&#xD;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+enum XmlNode {
+    Element(XmlElement),
+    Text(String),
+    CData(String),
+    Comment(String),
+    Pi(String, Option<String>),
+}
+
+struct XmlElement {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<XmlNode>,
+}
+
+struct XmlStyle {
+    pretty: bool,
+    indent: String,
+    wrap_attributes: Option<usize>,
+}
+
+impl XmlStyle {
+    fn write_node(&self, output: &mut String, node: &XmlNode, depth: usize) {
+        let indent = if self.pretty {
+            self.indent.repeat(depth)
+        } else {
+            String::new()
+        };
+        output.push_str(&indent);
+
+        match node {
+            XmlNode::Element(element) => self.write_element(output, element, depth, &indent),
+            XmlNode::Text(s) => output.push_str(&escape_text(s)),
+            XmlNode::CData(s) => {
+                output.push_str("<![CDATA[");
+                output.push_str(s);
+                output.push_str("]]>");
+            }
+            XmlNode::Comment(s) => {
+                output.push_str("<!--");
+                output.push_str(s);
+                output.push_str("-->");
+            }
+            XmlNode::Pi(name, data) => {
+                output.push_str("<?");
+                output.push_str(name);
+                if let Some(data) = data {
+                    output.push(' ');
+                    output.push_str(data);
+                }
+                output.push_str("?>");
+            }
+        }
+    }
+
+    fn write_element(&self, output: &mut String, element: &XmlElement, depth: usize, indent: &str) {
+        let attributes = element
+            .attributes
+            .iter()
+            .map(|(k, v)| format!("{k}=\"{}\"", escape_attr(v)))
+            .collect::<Vec<_>>();
+
+        let width = indent.len()
+            + element.name.len()
+            + attributes.iter().map(|a| a.len() + 1).sum::<usize>()
+            + 2;
+        let wrap = self.pretty
+            && attributes.len() > 1
+            && self.wrap_attributes.is_some_and(|max| width > max);
+
+        output.push('<');
+        output.push_str(&element.name);
+        for attr in &attributes {
+            if wrap {
+                output.push('\n');
+                output.push_str(indent);
+                output.push_str(&self.indent);
+            } else {
+                output.push(' ');
+            }
+            output.push_str(attr);
+        }
+        output.push('>');
+
+        // 只含文本的元素保持在同一行
+        let inline = !self.pretty
+            || element
+                .children
+                .iter()
+                .all(|c| matches!(c, XmlNode::Text(_) | XmlNode::CData(_)));
+
+        for child in &element.children {
+            if inline {
+                self.write_node(output, child, 0);
+            } else {
+                output.push('\n');
+                self.write_node(output, child, depth + 1);
+            }
+        }
+
+        if !inline {
+            output.push('\n');
+            output.push_str(indent);
+        }
+        output.push_str("</");
+        output.push_str(&element.name);
+        output.push('>');
+    }
+}
+
+fn escape_text(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn escape_attr(s: &str) -> String {
+    escape_text(s).replace('"', "&quot;")
+}
+
+/// 当前元素可见的命名空间，去掉 xml/xmlns 这两个内置前缀
+fn in_scope_namespaces(namespace: &BTreeMap<String, String>) -> BTreeMap<String, String> {
+    namespace
+        .iter()
+        .filter(|(prefix, uri)| {
+            prefix.as_str() != NS_XML_PREFIX
+                && prefix.as_str() != NS_XMLNS_PREFIX
+                && !(prefix.is_empty() && uri.is_empty())
+        })
+        .map(|(prefix, uri)| (prefix.to_owned(), uri.to_owned()))
+        .collect()
+}
+
+fn qualified_name(name: &OwnedName) -> String {
+    match &name.prefix {
+        Some(prefix) => format!("{prefix}:{}", name.local_name),
+        None => name.local_name.to_owned(),
+    }
+}
+
+fn xmlns_name(prefix: &str) -> String {
+    if prefix.is_empty() {
+        "xmlns".to_string()
+    } else {
+        format!("xmlns:{prefix}")
+    }
+}
+
+fn xml_error(e: &reader::Error) -> String {
+    let pos = e.position();
+    format!(
+        "line {}, column {}: {}",
+        pos.row + 1,
+        pos.column + 1,
+        e.msg()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // W3C Canonical XML 1.0 第 3 节和 Exclusive C14N 第 2.2 节的示例。
+    // xml-rs 不读取 DTD，依赖 DTD 的默认属性和属性类型的示例部分已去掉。
+
+    const PI_COMMENTS: &str = r#"<?xml version="1.0"?>
+
+<?xml-stylesheet   href="doc.xsl"
+   type="text/xsl"   ?>
+
+<!DOCTYPE doc SYSTEM "doc.dtd">
+
+<doc>Hello, world!<!-- Comment 1 --></doc>
+
+<?pi-without-data     ?>
+
+<!-- Comment 2 -->
+
+<!-- Comment 3 -->"#;
+
+    fn c14n(input: &str, algorithm: &str) -> String {
+        xml_canonicalize(input, algorithm, None, None, None).unwrap()
+    }
+
+    #[test]
+    fn pi_and_comments() {
+        assert_eq!(
+            c14n(PI_COMMENTS, "c14n"),
+            "<?xml-stylesheet href=\"doc.xsl\"\n   type=\"text/xsl\"   ?>\n\
+             <doc>Hello, world!</doc>\n\
+             <?pi-without-data?>"
+        );
+        assert_eq!(
+            c14n(
+                PI_COMMENTS,
+                "http://www.w3.org/TR/2001/REC-xml-c14n-20010315#WithComments"
+            ),
+            "<?xml-stylesheet href=\"doc.xsl\"\n   type=\"text/xsl\"   ?>\n\
+             <doc>Hello, world!<!-- Comment 1 --></doc>\n\
+             <?pi-without-data?>\n\
+             <!-- Comment 2 -->\n\
+             <!-- Comment 3 -->"
+        );
+    }
+
+    #[test]
+    fn whitespace_in_content() {
+        let input = "<doc>\n   <clean>   </clean>\n   <dirty>   A   B   </dirty>\n   <mixed>\n      A\n      <clean>   </clean>\n      B\n      <dirty>   A   B   </dirty>\n      C\n   </mixed>\n</doc>";
+        assert_eq!(c14n(input, "c14n"), input);
+    }
+
+    #[test]
+    fn start_and_end_tags() {
+        let input = r#"<doc>
+   <e1   />
+   <e2   ></e2>
+   <e3   name = "elem3"   id="elem3"   />
+   <e4   name="elem4"   id="elem4"   ></e4>
+   <e5 a:attr="out" b:attr="sorted" attr2="all" attr="I'm"
+      xmlns:b="http://www.ietf.org"
+      xmlns:a="http://www.w3.org"
+      xmlns="http://example.org"/>
+   <e6 xmlns="" xmlns:a="http://www.w3.org">
+      <e7 xmlns="http://www.ietf.org">
+         <e8 xmlns="" xmlns:a="http://www.w3.org">
+            <e9 xmlns="" xmlns:a="http://www.ietf.org"/>
+         </e8>
+      </e7>
+   </e6>
+</doc>"#;
+        let expected = r#"<doc>
+   <e1></e1>
+   <e2></e2>
+   <e3 id="elem3" name="elem3"></e3>
+   <e4 id="elem4" name="elem4"></e4>
+   <e5 xmlns="http://example.org" xmlns:a="http://www.w3.org" xmlns:b="http://www.ietf.org" attr="I'm" attr2="all" b:attr="sorted" a:attr="out"></e5>
+   <e6 xmlns:a="http://www.w3.org">
+      <e7 xmlns="http://www.ietf.org">
+         <e8 xmlns="">
+            <e9 xmlns:a="http://www.ietf.org"></e9>
+         </e8>
+      </e7>
+   </e6>
+</doc>"#;
+        assert_eq!(c14n(input, "c14n"), expected);
+    }
+
+    #[test]
+    fn character_references() {
+        let input = r#"<doc>
+   <text>First line&#x0d;&#10;Second line</text>
+   <value>&#x32;</value>
+   <compute><![CDATA[value>"0" && value<"10" ?"valid":"error"]]></compute>
+   <compute expr='value>"0" &amp;&amp; value&lt;"10" ?"valid":"error"'>valid</compute>
+   <norm attr=' &apos;   &#x20;&#13;&#xa;&#9;   &apos; '/>
+</doc>"#;
+        let expected = r#"<doc>
+   <text>First line&#xD;
+Second line</text>
+   <value>2</value>
+   <compute>value&gt;"0" &amp;&amp; value&lt;"10" ?"valid":"error"</compute>
+   <compute expr="value>&quot;0&quot; &amp;&amp; value&lt;&quot;10&quot; ?&quot;valid&quot;:&quot;error&quot;">valid</compute>
+   <norm attr=" '    &#xD;&#xA;&#x9;   ' "></norm>
+</doc>"#;
+        assert_eq!(c14n(input, "c14n"), expected);
+    }
+
+    const EXCLUSIVE: &str = r#"<n0:local xmlns:n0="foo:bar" xmlns:n3="ftp://example.org">
+  <n1:elem2 xmlns:n1="http://example.net" xml:lang="en" Id="e2">
+    <n3:stuff xmlns:n3="ftp://example.org"/>
+  </n1:elem2>
+</n0:local>"#;
+
+    #[test]
+    fn exclusive_subset() {
+        let subset = |algorithm: &str, prefixes: Option<&str>| {
+            xml_canonicalize(
+                EXCLUSIVE,
+                algorithm,
+                None,
+                prefixes.map(str::to_string),
+                Some("#e2".to_string()),
+            )
+            .unwrap()
+        };
+        assert_eq!(
+            subset("c14n", None),
+            "<n1:elem2 xmlns:n0=\"foo:bar\" xmlns:n1=\"http://example.net\" xmlns:n3=\"ftp://example.org\" Id=\"e2\" xml:lang=\"en\">\n    \
+             <n3:stuff></n3:stuff>\n  \
+             </n1:elem2>"
+        );
+        assert_eq!(
+            subset("exc-c14n", None),
+            "<n1:elem2 xmlns:n1=\"http://example.net\" Id=\"e2\" xml:lang=\"en\">\n    \
+             <n3:stuff xmlns:n3=\"ftp://example.org\"></n3:stuff>\n  \
+             </n1:elem2>"
+        );
+        assert_eq!(
+            subset("exc-c14n", Some("n0")),
+            "<n1:elem2 xmlns:n0=\"foo:bar\" xmlns:n1=\"http://example.net\" Id=\"e2\" xml:lang=\"en\">\n    \
+             <n3:stuff xmlns:n3=\"ftp://example.org\"></n3:stuff>\n  \
+             </n1:elem2>"
+        );
+    }
+}
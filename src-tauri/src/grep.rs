@@ -0,0 +1,281 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use ignore::{overrides::OverrideBuilder, WalkBuilder};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+
+use crate::regex::{Engine, RegexCache, RegexFlags, Span};
+
+/// 每个匹配行发送的事件名
+pub const GREP_EVENT: &str = "regex-grep-match";
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct GrepOptions {
+    /// 只搜索匹配这些 glob 的文件，如 `*.rs`
+    pub include: Vec<String>,
+    /// 跳过匹配这些 glob 的文件或目录
+    pub exclude: Vec<String>,
+    /// 匹配行前后的上下文行数
+    pub context: usize,
+    /// 搜索隐藏文件和目录
+    pub hidden: bool,
+    /// 不读取 `.gitignore`、`.ignore`
+    pub no_ignore: bool,
+    /// 最多报告的匹配行数，0 表示不限
+    pub max_count: usize,
+    /// 跳过大于此字节数的文件
+    pub max_filesize: u64,
+}
+
+impl Default for GrepOptions {
+    fn default() -> Self {
+        GrepOptions {
+            include: vec![],
+            exclude: vec![],
+            context: 0,
+            hidden: false,
+            no_ignore: false,
+            max_count: 10_000,
+            max_filesize: 10 * 1024 * 1024,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct GrepMatch {
+    /// 调用方传入的搜索编号，用于区分并发的搜索
+    pub id: u64,
+    pub path: String,
+    /// 从 1 开始
+    pub line: usize,
+    /// 第一个匹配的列，从 1 开始按字符计
+    pub column: usize,
+    pub text: String,
+    /// 行内所有匹配的位置
+    pub spans: Vec<Span>,
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct GrepSummary {
+    pub files_searched: usize,
+    pub files_matched: usize,
+    pub binary_skipped: usize,
+    /// 无法读取的文件或目录
+    pub errors: Vec<String>,
+    pub matches: usize,
+    /// 达到 `max_count` 后提前结束
+    pub truncated: bool,
+    /// 被 `regex_grep_cancel` 取消
+    pub cancelled: bool,
+}
+
+/// 正在执行的搜索的取消标志，按搜索编号记录，作为 Tauri managed state 共享
+#[derive(Default)]
+pub struct GrepJobs {
+    running: Mutex<HashMap<u64, Arc<AtomicBool>>>,
+}
+
+/// 在文件或目录树中按行搜索，遵循 `.gitignore`，匹配通过 `regex-grep-match` 事件逐行发送
+///
+/// 搜索在阻塞线程池中执行，结束后返回统计信息。
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn regex_grep(
+    app: AppHandle,
+    cache: State<'_, RegexCache>,
+    jobs: State<'_, GrepJobs>,
+    id: u64,
+    path: String,
+    pattern: String,
+    flags: Option<RegexFlags>,
+    options: Option<GrepOptions>,
+) -> Result<GrepSummary, String> {
+    let re = cache.get(&pattern, flags.unwrap_or_default())?;
+    let options = options.unwrap_or_default();
+    if !Path::new(&path).exists() {
+        return Err(format!("{path}: no such file or directory"));
+    }
+
+    let cancel = Arc::new(AtomicBool::new(false));
+    match jobs
+        .running
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .entry(id)
+    {
+        // 覆盖会让先前的搜索无法再取消
+        Entry::Occupied(_) => return Err(format!("search {id} is already running")),
+        Entry::Vacant(entry) => {
+            entry.insert(cancel.clone());
+        }
+    }
+
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        grep(&path, &re, &options, &cancel, |m| {
+            let _ = app.emit(GREP_EVENT, GrepMatch { id, ..m });
+        })
+    })
+    .await
+    .map_err(|e| e.to_string());
+
+    jobs.running
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(&id);
+    result?
+}
+
+/// 取消正在执行的搜索，搜索不存在或已结束时返回 false
+#[tauri::command]
+pub fn regex_grep_cancel(jobs: State<'_, GrepJobs>, id: u64) -> bool {
+    match jobs
+        .running
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&id)
+    {
+        Some(cancel) => {
+            cancel.store(true, Ordering::Relaxed);
+            true
+        }
+        None => false,
+    }
+}
+
+fn grep(
+    root: &str,
+    re: &Engine,
+    options: &GrepOptions,
+    cancel: &AtomicBool,
+    mut emit: impl FnMut(GrepMatch),
+) -> Result<GrepSummary, String> {
+    let mut overrides = OverrideBuilder::new(root);
+    for glob in &options.include {
+        overrides.add(glob).map_err(|e| e.to_string())?;
+    }
+    for glob in &options.exclude {
+        overrides
+            .add(&format!("!{glob}"))
+            .map_err(|e| e.to_string())?;
+    }
+    let overrides = overrides.build().map_err(|e| e.to_string())?;
+
+    let walker = WalkBuilder::new(root)
+        .hidden(!options.hidden)
+        .git_ignore(!options.no_ignore)
+        .git_global(!options.no_ignore)
+        .git_exclude(!options.no_ignore)
+        .ignore(!options.no_ignore)
+        // 不在 git 仓库里也读取 .gitignore
+        .require_git(false)
+        .max_filesize(Some(options.max_filesize))
+        .overrides(overrides)
+        .sort_by_file_path(|a, b| a.cmp(b))
+        .build();
+
+    let mut summary = GrepSummary::default();
+    for entry in walker {
+        if cancel.load(Ordering::Relaxed) {
+            summary.cancelled = true;
+            return Ok(summary);
+        }
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                summary.errors.push(e.to_string());
+                continue;
+            }
+        };
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+
+        let path = entry.path();
+        let bytes = match std::fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                summary.errors.push(format!("{}: {e}", path.display()));
+                continue;
+            }
+        };
+        // 与 grep 相同，前 8 KiB 出现 NUL 视为二进制文件
+        if bytes[..bytes.len().min(8192)].contains(&0) {
+            summary.binary_skipped += 1;
+            continue;
+        }
+        summary.files_searched += 1;
+
+        let text = String::from_utf8_lossy(&bytes);
+        let lines = text.lines().collect::<Vec<_>>();
+        let mut file_matched = false;
+        for (n, line) in lines.iter().enumerate() {
+            if cancel.load(Ordering::Relaxed) {
+                summary.cancelled = true;
+                break;
+            }
+            // fancy 引擎可能在个别行上超出回溯上限，记录后继续搜索
+            let spans = match line_spans(re, line) {
+                Ok(spans) => spans,
+                Err(e) => {
+                    summary
+                        .errors
+                        .push(format!("{}:{}: {e}", path.display(), n + 1));
+                    continue;
+                }
+            };
+            if spans.is_empty() {
+                continue;
+            }
+            if options.max_count > 0 && summary.matches == options.max_count {
+                summary.truncated = true;
+                return Ok(summary);
+            }
+
+            file_matched = true;
+            summary.matches += 1;
+            let context = |range: std::ops::Range<usize>| {
+                lines[range].iter().map(|l| l.to_string()).collect()
+            };
+            emit(GrepMatch {
+                id: 0,
+                path: path.display().to_string(),
+                line: n + 1,
+                column: spans[0].char_start + 1,
+                text: line.to_string(),
+                before: context(n.saturating_sub(options.context)..n),
+                after: context(n + 1..(n + 1 + options.context).min(lines.len())),
+                spans,
+            });
+        }
+        if file_matched {
+            summary.files_matched += 1;
+        }
+        if summary.cancelled {
+            break;
+        }
+    }
+
+    Ok(summary)
+}
+
+fn line_spans(re: &Engine, line: &str) -> Result<Vec<Span>, String> {
+    let mut spans = vec![];
+    for caps in re.captures_iter(line, usize::MAX)? {
+        let (start, end) = caps.get(0).expect("group 0 always participates");
+        let char_start = line[..start].chars().count();
+        spans.push(Span {
+            start,
+            end,
+            char_start,
+            char_end: char_start + line[start..end].chars().count(),
+        });
+    }
+    Ok(spans)
+}
@@ -0,0 +1,390 @@
+use base64::{engine::general_purpose, Engine as _};
+use pkcs8::der::pem::LineEnding;
+use pkcs8::der::Encode;
+use pkcs8::{AssociatedOid, EncodePrivateKey, EncodePublicKey, ObjectIdentifier, PrivateKeyInfo};
+use rand::RngCore;
+use rsa::pkcs1::{EncodeRsaPrivateKey, EncodeRsaPublicKey};
+use rsa::traits::{PrivateKeyParts, PublicKeyParts};
+use rsa::{BigUint, RsaPrivateKey};
+use sec1::{EcParameters, EcPrivateKey};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use ssh_key::private::{EcdsaKeypair, KeypairData, RsaKeypair};
+use ssh_key::HashAlg;
+
+use crate::random::Source;
+
+/// PKCS#8 口令加密的 PBKDF2 迭代次数
+const PBKDF2_ITERATIONS: u32 = 600_000;
+
+/// X25519 的 PKCS#8、SPKI 前缀，OID 1.3.101.110，后接 32 字节密钥
+const X25519_PKCS8_PREFIX: [u8; 16] = [
+    0x30, 0x2e, 0x02, 0x01, 0x00, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x6e, 0x04, 0x22, 0x04, 0x20,
+];
+const X25519_SPKI_PREFIX: [u8; 12] = [
+    0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x6e, 0x03, 0x21, 0x00,
+];
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct KeyOptions {
+    /// `rsa`、`p256`、`p384`、`ed25519`、`x25519`
+    pub algorithm: String,
+    /// RSA 位数，2048 到 4096
+    pub bits: usize,
+    /// `pkcs8`、`pkcs1`（RSA）、`sec1`（ECDSA）、`openssh`、`jwk`
+    pub format: String,
+    /// 输出 base64 编码的 DER，只用于 pkcs8、pkcs1、sec1
+    pub der: bool,
+    /// 加密私钥的口令，只用于 pkcs8、openssh
+    pub passphrase: Option<String>,
+    /// OpenSSH 密钥的注释
+    pub comment: String,
+}
+
+impl Default for KeyOptions {
+    fn default() -> Self {
+        KeyOptions {
+            algorithm: "ed25519".to_string(),
+            bits: 2048,
+            format: "pkcs8".to_string(),
+            der: false,
+            passphrase: None,
+            comment: String::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct KeyPair {
+    pub private_key: String,
+    pub public_key: String,
+    /// OpenSSH 公钥的 SHA256 指纹，X25519 没有
+    pub fingerprint: Option<String>,
+}
+
+enum Key {
+    Rsa(Box<RsaPrivateKey>),
+    P256(p256::SecretKey),
+    P384(p384::SecretKey),
+    Ed25519(ed25519_dalek::SigningKey),
+    X25519(x25519_dalek::StaticSecret),
+}
+
+/// 生成非对称密钥对，RSA 在阻塞线程池中生成
+#[tauri::command]
+pub async fn gen_keypair(
+    options: Option<KeyOptions>,
+    seed: Option<u64>,
+) -> Result<KeyPair, String> {
+    let options = options.unwrap_or_default();
+    let passphrase = options.passphrase.as_deref().filter(|p| !p.is_empty());
+    match options.format.as_str() {
+        "pkcs8" => {}
+        "pkcs1" if options.algorithm != "rsa" => {
+            return Err("PKCS#1 is only available for RSA keys".to_string())
+        }
+        "sec1" if !matches!(options.algorithm.as_str(), "p256" | "p384") => {
+            return Err("SEC1 is only available for ECDSA keys".to_string())
+        }
+        "pkcs1" | "sec1" if passphrase.is_some() => {
+            return Err(
+                "PKCS#1 and SEC1 keys cannot be encrypted, use PKCS#8 or OpenSSH".to_string(),
+            )
+        }
+        "pkcs1" | "sec1" => {}
+        "openssh" if options.algorithm == "x25519" => {
+            return Err("X25519 keys have no OpenSSH format".to_string())
+        }
+        "openssh" | "jwk" if options.der => {
+            return Err("DER output is only available for PKCS#8, PKCS#1 and SEC1".to_string())
+        }
+        "jwk" if passphrase.is_some() => {
+            return Err("JWK output cannot be encrypted, use PKCS#8 or OpenSSH".to_string())
+        }
+        "openssh" | "jwk" => {}
+        other => return Err(format!("unknown key format: {other}")),
+    }
+    if options.algorithm == "rsa" && !(2048..=4096).contains(&options.bits) {
+        return Err(format!(
+            "RSA key size must be between 2048 and 4096 bits, got {}",
+            options.bits
+        ));
+    }
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut rng = Source::new(seed);
+        let key = generate(&options, &mut rng)?;
+        let passphrase = options.passphrase.as_deref().filter(|p| !p.is_empty());
+        let (private_key, public_key) = match options.format.as_str() {
+            "pkcs8" => pkcs8_pair(&key, passphrase, options.der, &mut rng)?,
+            "pkcs1" | "sec1" => legacy_pair(&key, options.der)?,
+            "openssh" => {
+                let mut private = ssh_private_key(&key, &options.comment)?.expect("not X25519");
+                // 加密后的私钥不再带注释，先导出公钥
+                let public = private
+                    .public_key()
+                    .to_openssh()
+                    .map_err(|e| e.to_string())?;
+                if let Some(passphrase) = passphrase {
+                    private = private
+                        .encrypt(&mut rng, passphrase)
+                        .map_err(|e| e.to_string())?;
+                }
+                let private = private
+                    .to_openssh(LineEnding::LF)
+                    .map_err(|e| e.to_string())?;
+                (private.to_string(), public)
+            }
+            _ => jwk_pair(&key)?,
+        };
+        let fingerprint = ssh_private_key(&key, "")?
+            .map(|k| k.public_key().fingerprint(HashAlg::Sha256).to_string());
+
+        Ok(KeyPair {
+            private_key,
+            public_key,
+            fingerprint,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+fn generate(options: &KeyOptions, rng: &mut Source) -> Result<Key, String> {
+    Ok(match options.algorithm.as_str() {
+        "rsa" => Key::Rsa(Box::new(
+            RsaPrivateKey::new(rng, options.bits).map_err(|e| e.to_string())?,
+        )),
+        "p256" => Key::P256(p256::SecretKey::random(rng)),
+        "p384" => Key::P384(p384::SecretKey::random(rng)),
+        "ed25519" => Key::Ed25519(ed25519_dalek::SigningKey::generate(rng)),
+        "x25519" => Key::X25519(x25519_dalek::StaticSecret::random_from_rng(rng)),
+        other => return Err(format!("unknown key algorithm: {other}")),
+    })
+}
+
+/// PKCS#8 私钥和 SPKI 公钥，有口令时使用 PBES2（PBKDF2-SHA256 + AES-256-CBC）加密
+fn pkcs8_pair(
+    key: &Key,
+    passphrase: Option<&str>,
+    der: bool,
+    rng: &mut Source,
+) -> Result<(String, String), String> {
+    let (private, public) = match key {
+        Key::Rsa(k) => (
+            k.to_pkcs8_der()
+                .map_err(|e| e.to_string())?
+                .as_bytes()
+                .to_vec(),
+            k.to_public_key()
+                .to_public_key_der()
+                .map_err(|e| e.to_string())?
+                .into_vec(),
+        ),
+        Key::P256(k) => (
+            k.to_pkcs8_der()
+                .map_err(|e| e.to_string())?
+                .as_bytes()
+                .to_vec(),
+            k.public_key()
+                .to_public_key_der()
+                .map_err(|e| e.to_string())?
+                .into_vec(),
+        ),
+        Key::P384(k) => (
+            k.to_pkcs8_der()
+                .map_err(|e| e.to_string())?
+                .as_bytes()
+                .to_vec(),
+            k.public_key()
+                .to_public_key_der()
+                .map_err(|e| e.to_string())?
+                .into_vec(),
+        ),
+        Key::Ed25519(k) => (
+            k.to_pkcs8_der()
+                .map_err(|e| e.to_string())?
+                .as_bytes()
+                .to_vec(),
+            k.verifying_key()
+                .to_public_key_der()
+                .map_err(|e| e.to_string())?
+                .into_vec(),
+        ),
+        Key::X25519(k) => (
+            [&X25519_PKCS8_PREFIX[..], &k.to_bytes()].concat(),
+            [
+                &X25519_SPKI_PREFIX[..],
+                x25519_dalek::PublicKey::from(k).as_bytes(),
+            ]
+            .concat(),
+        ),
+    };
+
+    let (private, label) = match passphrase {
+        Some(passphrase) => {
+            let mut salt = [0u8; 16];
+            let mut iv = [0u8; 16];
+            rng.fill_bytes(&mut salt);
+            rng.fill_bytes(&mut iv);
+            let params = pkcs8::pkcs5::pbes2::Parameters::pbkdf2_sha256_aes256cbc(
+                PBKDF2_ITERATIONS,
+                &salt,
+                &iv,
+            )
+            .map_err(|e| e.to_string())?;
+            let encrypted = PrivateKeyInfo::try_from(private.as_slice())
+                .map_err(|e| e.to_string())?
+                .encrypt_with_params(params, passphrase)
+                .map_err(|e| e.to_string())?;
+            (encrypted.as_bytes().to_vec(), "ENCRYPTED PRIVATE KEY")
+        }
+        None => (private, "PRIVATE KEY"),
+    };
+
+    Ok((
+        encode(label, &private, der)?,
+        encode("PUBLIC KEY", &public, der)?,
+    ))
+}
+
+/// RSA 用 PKCS#1，ECDSA 私钥用 SEC1、公钥用 SPKI
+fn legacy_pair(key: &Key, der: bool) -> Result<(String, String), String> {
+    match key {
+        Key::Rsa(k) => {
+            let private = k.to_pkcs1_der().map_err(|e| e.to_string())?;
+            let public = k
+                .to_public_key()
+                .to_pkcs1_der()
+                .map_err(|e| e.to_string())?;
+            Ok((
+                encode("RSA PRIVATE KEY", private.as_bytes(), der)?,
+                encode("RSA PUBLIC KEY", public.as_bytes(), der)?,
+            ))
+        }
+        Key::P256(k) => {
+            let private = sec1_der(
+                p256::NistP256::OID,
+                &k.to_bytes(),
+                p256::EncodedPoint::from(k.public_key()).as_bytes(),
+            )?;
+            let public = k
+                .public_key()
+                .to_public_key_der()
+                .map_err(|e| e.to_string())?;
+            Ok((
+                encode("EC PRIVATE KEY", &private, der)?,
+                encode("PUBLIC KEY", public.as_bytes(), der)?,
+            ))
+        }
+        Key::P384(k) => {
+            let private = sec1_der(
+                p384::NistP384::OID,
+                &k.to_bytes(),
+                p384::EncodedPoint::from(k.public_key()).as_bytes(),
+            )?;
+            let public = k
+                .public_key()
+                .to_public_key_der()
+                .map_err(|e| e.to_string())?;
+            Ok((
+                encode("EC PRIVATE KEY", &private, der)?,
+                encode("PUBLIC KEY", public.as_bytes(), der)?,
+            ))
+        }
+        _ => unreachable!("checked before generating"),
+    }
+}
+
+/// `to_sec1_der` 不带曲线参数，OpenSSL 无法读取，这里补上曲线 OID 和公钥
+fn sec1_der(curve: ObjectIdentifier, private: &[u8], public: &[u8]) -> Result<Vec<u8>, String> {
+    EcPrivateKey {
+        private_key: private,
+        parameters: Some(EcParameters::NamedCurve(curve)),
+        public_key: Some(public),
+    }
+    .to_der()
+    .map_err(|e| e.to_string())
+}
+
+/// PEM 或 base64 编码的 DER
+fn encode(label: &str, der: &[u8], raw: bool) -> Result<String, String> {
+    if raw {
+        return Ok(general_purpose::STANDARD.encode(der));
+    }
+    pkcs8::der::pem::encode_string(label, LineEnding::LF, der).map_err(|e| e.to_string())
+}
+
+/// X25519 返回 None
+fn ssh_private_key(key: &Key, comment: &str) -> Result<Option<ssh_key::PrivateKey>, String> {
+    let data = match key {
+        Key::Rsa(k) => {
+            KeypairData::from(RsaKeypair::try_from(k.as_ref()).map_err(|e| e.to_string())?)
+        }
+        Key::P256(k) => KeypairData::from(EcdsaKeypair::NistP256 {
+            public: k.public_key().into(),
+            private: k.clone().into(),
+        }),
+        Key::P384(k) => KeypairData::from(EcdsaKeypair::NistP384 {
+            public: k.public_key().into(),
+            private: k.clone().into(),
+        }),
+        Key::Ed25519(k) => KeypairData::from(ssh_key::private::Ed25519Keypair::from(k)),
+        Key::X25519(_) => return Ok(None),
+    };
+    ssh_key::PrivateKey::new(data, comment)
+        .map(Some)
+        .map_err(|e| e.to_string())
+}
+
+fn jwk_pair(key: &Key) -> Result<(String, String), String> {
+    let b64 = |bytes: &[u8]| general_purpose::URL_SAFE_NO_PAD.encode(bytes);
+    let big = |n: &BigUint| b64(&n.to_bytes_be());
+
+    let (private, public) = match key {
+        Key::Rsa(k) => {
+            let public = json!({ "kty": "RSA", "n": big(k.n()), "e": big(k.e()) });
+            let primes = k.primes();
+            let mut private = public.clone();
+            private["d"] = json!(big(k.d()));
+            private["p"] = json!(big(&primes[0]));
+            private["q"] = json!(big(&primes[1]));
+            if let (Some(dp), Some(dq), Some(qi)) = (k.dp(), k.dq(), k.crt_coefficient()) {
+                private["dp"] = json!(big(dp));
+                private["dq"] = json!(big(dq));
+                private["qi"] = json!(big(&qi));
+            }
+            (private, public)
+        }
+        Key::P256(k) => (
+            parse_jwk(&k.to_jwk_string())?,
+            parse_jwk(&k.public_key().to_jwk_string())?,
+        ),
+        Key::P384(k) => (
+            parse_jwk(&k.to_jwk_string())?,
+            parse_jwk(&k.public_key().to_jwk_string())?,
+        ),
+        Key::Ed25519(k) => {
+            let public =
+                json!({ "kty": "OKP", "crv": "Ed25519", "x": b64(k.verifying_key().as_bytes()) });
+            let mut private = public.clone();
+            private["d"] = json!(b64(k.as_bytes()));
+            (private, public)
+        }
+        Key::X25519(k) => {
+            let x = x25519_dalek::PublicKey::from(k);
+            let public = json!({ "kty": "OKP", "crv": "X25519", "x": b64(x.as_bytes()) });
+            let mut private = public.clone();
+            private["d"] = json!(b64(&k.to_bytes()));
+            (private, public)
+        }
+    };
+
+    let pretty = |v: &Value| serde_json::to_string_pretty(v).map_err(|e| e.to_string());
+    Ok((pretty(&private)?, pretty(&public)?))
+}
+
+fn parse_jwk(jwk: &str) -> Result<Value, String> {
+    serde_json::from_str(jwk).map_err(|e| e.to_string())
+}
@@ -3,17 +3,34 @@
 
 
 mod codec;
+mod codegen;
+mod diff;
 mod format;
+mod grep;
 mod http;
+mod keygen;
+mod otp;
 mod regex;
+mod regex_explain;
+mod regex_gen;
+mod regex_suite;
 mod x509;
 mod random;
+mod passphrase;
+mod strength;
 mod dns;
+mod sql;
+mod table;
+mod testdata;
+mod token;
+mod wildcard;
 
 fn main() {
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_clipboard_manager::init())
+        .manage(regex::RegexCache::new(64))
+        .manage(grep::GrepJobs::default())
         .invoke_handler(tauri::generate_handler![
             codec::base64_decode,
             codec::base64_encode,
@@ -21,14 +38,49 @@ fn main() {
             codec::url_encode,
             regex::regex_is_match,
             regex::regex_captures,
+            regex::regex_find_all,
+            regex::regex_replace,
+            regex::regex_split,
+            regex::regex_cache_stats,
+            regex::regex_cache_clear,
+            grep::regex_grep,
+            grep::regex_grep_cancel,
+            regex_explain::regex_explain,
+            regex_gen::regex_generate,
+            regex_suite::regex_test_suite,
+            regex_suite::regex_suite_load,
+            regex_suite::regex_suite_save,
+            wildcard::wildcard_to_regex,
             format::json_format,
             format::xml_format,
+            format::xml_canonicalize,
+            sql::sql_format,
+            table::csv_convert,
+            table::json_to_csv,
+            codegen::json_codegen,
+            diff::text_diff,
             http::http_request,
             http::ipinfo,
             x509::x509_parse,
+            keygen::gen_keypair,
+            otp::otp_codes,
+            otp::gen_otp_secret,
             random::gen_passwords,
             random::gen_uuid_v4,
             random::gen_uuid_v7,
+            random::gen_uuids,
+            random::inspect_uuid,
+            random::gen_ulids,
+            random::gen_ksuids,
+            random::gen_nanoids,
+            random::gen_snowflakes,
+            random::decode_id,
+            passphrase::gen_passphrases,
+            strength::password_strength,
+            token::gen_random_bytes,
+            token::gen_tokens,
+            token::validate_token,
+            testdata::gen_fake_data,
             dns::dns_lookup,
         ])
         .run(tauri::generate_context!())
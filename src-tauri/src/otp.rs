@@ -0,0 +1,267 @@
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use hmac::digest::KeyInit;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+use sha2::{Sha256, Sha512};
+use url::Url;
+
+use crate::codec::{base32_decode, base32_encode};
+use crate::random::Source;
+
+/// 前后最多计算的相邻验证码个数
+const MAX_WINDOW: u64 = 10;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(default)]
+pub struct OtpParams {
+    /// `totp` 或 `hotp`
+    pub kind: String,
+    /// `SHA1`、`SHA256`、`SHA512`
+    pub algorithm: String,
+    /// 6 到 8 位
+    pub digits: u32,
+    /// TOTP 时间步长（秒）
+    pub period: u64,
+    /// HOTP 计数器
+    pub counter: u64,
+    pub issuer: String,
+    pub account: String,
+}
+
+impl Default for OtpParams {
+    fn default() -> Self {
+        OtpParams {
+            kind: "totp".to_string(),
+            algorithm: "SHA1".to_string(),
+            digits: 6,
+            period: 30,
+            counter: 0,
+            issuer: String::new(),
+            account: String::new(),
+        }
+    }
+}
+
+impl OtpParams {
+    fn check(&mut self) -> Result<(), String> {
+        self.kind = self.kind.to_ascii_lowercase();
+        self.algorithm = self.algorithm.to_ascii_uppercase().replace('-', "");
+        if !matches!(self.kind.as_str(), "totp" | "hotp") {
+            return Err(format!("unknown OTP type: {}", self.kind));
+        }
+        if !matches!(self.algorithm.as_str(), "SHA1" | "SHA256" | "SHA512") {
+            return Err(format!("unknown OTP algorithm: {}", self.algorithm));
+        }
+        if !(6..=8).contains(&self.digits) {
+            return Err(format!(
+                "digits must be between 6 and 8, got {}",
+                self.digits
+            ));
+        }
+        if self.period == 0 {
+            return Err("period must be at least 1 second".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct OtpCode {
+    /// 相对当前计数器的偏移，0 为当前验证码
+    pub offset: i64,
+    pub counter: u64,
+    pub code: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct OtpCodes {
+    /// 合并 URI 参数后实际使用的参数
+    pub params: OtpParams,
+    pub counter: u64,
+    pub codes: Vec<OtpCode>,
+    /// TOTP 当前验证码剩余的有效秒数
+    pub remaining: Option<u64>,
+}
+
+/// 计算 TOTP（RFC 6238）或 HOTP（RFC 4226）验证码
+///
+/// `secret` 可以是 base32 密钥或 `otpauth://` URI，URI 中的参数优先于 `params`。
+/// `window` 为前后各计算的相邻验证码个数，`time` 为 Unix 秒，默认当前时间。
+#[tauri::command]
+pub fn otp_codes(
+    secret: &str,
+    params: Option<OtpParams>,
+    window: Option<u64>,
+    time: Option<u64>,
+) -> Result<OtpCodes, String> {
+    let mut params = params.unwrap_or_default();
+    let secret = match secret.trim() {
+        uri if uri.starts_with("otpauth://") => parse_uri(uri, &mut params)?,
+        secret => secret.to_string(),
+    };
+    params.check()?;
+    let key = base32_decode(&secret)?;
+    if key.is_empty() {
+        return Err("secret is empty".to_string());
+    }
+
+    let (counter, remaining) = if params.kind == "totp" {
+        let now = match time {
+            Some(time) => time,
+            None => SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_err(|e| e.to_string())?
+                .as_secs(),
+        };
+        (
+            now / params.period,
+            Some(params.period - now % params.period),
+        )
+    } else {
+        (params.counter, None)
+    };
+
+    let window = window.unwrap_or(1).min(MAX_WINDOW);
+    let codes = (counter.saturating_sub(window)..=counter.saturating_add(window))
+        .map(|c| OtpCode {
+            offset: c as i64 - counter as i64,
+            counter: c,
+            code: hotp(&key, c, &params.algorithm, params.digits),
+        })
+        .collect();
+
+    Ok(OtpCodes {
+        params,
+        counter,
+        codes,
+        remaining,
+    })
+}
+
+#[derive(Serialize, Debug)]
+pub struct OtpSecret {
+    /// 无填充的 base32 密钥
+    pub secret: String,
+    pub uri: String,
+}
+
+/// 生成新的 OTP 密钥和 `otpauth://` URI
+///
+/// `length` 为密钥字节数，默认取算法的摘要长度（SHA1 20、SHA256 32、SHA512 64）。
+#[tauri::command]
+pub fn gen_otp_secret(
+    params: Option<OtpParams>,
+    length: Option<usize>,
+    seed: Option<u64>,
+) -> Result<OtpSecret, String> {
+    let mut params = params.unwrap_or_default();
+    params.check()?;
+    let length = length.unwrap_or(match params.algorithm.as_str() {
+        "SHA256" => 32,
+        "SHA512" => 64,
+        _ => 20,
+    });
+    if !(10..=128).contains(&length) {
+        return Err(format!(
+            "secret length must be between 10 and 128 bytes, got {length}"
+        ));
+    }
+
+    let mut key = vec![0u8; length];
+    Source::new(seed)
+        .try_fill_bytes(&mut key)
+        .map_err(|e| e.to_string())?;
+    let secret = base32_encode(&key, false);
+    let uri = otp_uri(&secret, &params);
+    Ok(OtpSecret { secret, uri })
+}
+
+/// 按 Key Uri Format 生成 `otpauth://TYPE/ISSUER:ACCOUNT?secret=...`
+fn otp_uri(secret: &str, params: &OtpParams) -> String {
+    let label = match (params.issuer.as_str(), params.account.as_str()) {
+        ("", account) => urlencoding::encode(account).to_string(),
+        (issuer, account) => format!(
+            "{}:{}",
+            urlencoding::encode(issuer),
+            urlencoding::encode(account)
+        ),
+    };
+    let mut uri = format!(
+        "otpauth://{}/{label}?secret={secret}&algorithm={}&digits={}",
+        params.kind, params.algorithm, params.digits
+    );
+    if params.kind == "totp" {
+        uri.push_str(&format!("&period={}", params.period));
+    } else {
+        uri.push_str(&format!("&counter={}", params.counter));
+    }
+    if !params.issuer.is_empty() {
+        uri.push_str(&format!("&issuer={}", urlencoding::encode(&params.issuer)));
+    }
+    uri
+}
+
+/// 解析 `otpauth://` URI，把参数写入 `params`，返回 base32 密钥
+fn parse_uri(uri: &str, params: &mut OtpParams) -> Result<String, String> {
+    let url = Url::parse(uri).map_err(|e| format!("invalid otpauth URI: {e}"))?;
+    params.kind = url.host_str().unwrap_or_default().to_string();
+
+    let label =
+        urlencoding::decode(url.path().trim_start_matches('/')).map_err(|e| e.to_string())?;
+    match label.split_once(':') {
+        Some((issuer, account)) => {
+            params.issuer = issuer.trim().to_string();
+            params.account = account.trim().to_string();
+        }
+        None => params.account = label.trim().to_string(),
+    }
+
+    let mut secret = None;
+    for (key, value) in url.query_pairs() {
+        match key.as_ref() {
+            "secret" => secret = Some(value.to_string()),
+            // issuer 参数优先于标签中的前缀
+            "issuer" => params.issuer = value.to_string(),
+            "algorithm" => params.algorithm = value.to_string(),
+            "digits" => params.digits = number(&key, &value)?,
+            "period" => params.period = number(&key, &value)?,
+            "counter" => params.counter = number(&key, &value)?,
+            _ => {}
+        }
+    }
+    secret.ok_or_else(|| "otpauth URI has no secret".to_string())
+}
+
+/// RFC 4226 动态截断
+fn hotp(key: &[u8], counter: u64, algorithm: &str, digits: u32) -> String {
+    let msg = counter.to_be_bytes();
+    let digest = match algorithm {
+        "SHA256" => hmac::<Hmac<Sha256>>(key, &msg),
+        "SHA512" => hmac::<Hmac<Sha512>>(key, &msg),
+        _ => hmac::<Hmac<Sha1>>(key, &msg),
+    };
+    let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+    let bin =
+        u32::from_be_bytes(digest[offset..offset + 4].try_into().expect("4 bytes")) & 0x7fff_ffff;
+    format!(
+        "{:0width$}",
+        bin % 10u32.pow(digits),
+        width = digits as usize
+    )
+}
+
+fn hmac<M: Mac + KeyInit>(key: &[u8], msg: &[u8]) -> Vec<u8> {
+    let mut mac = <M as KeyInit>::new_from_slice(key).expect("HMAC takes any key size");
+    mac.update(msg);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn number<T: FromStr>(key: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid {key} in otpauth URI: {value}"))
+}
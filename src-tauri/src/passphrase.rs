@@ -0,0 +1,154 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::random::Source;
+
+// EFF 词表（https://www.eff.org/dice）
+const EFF_LARGE: &str = include_str!("../wordlists/eff_large_wordlist.txt");
+const EFF_SHORT: &str = include_str!("../wordlists/eff_short_wordlist_1.txt");
+
+const SYMBOLS: &[u8] = b"!@#$%^&*-_=+?";
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct PassphraseOptions {
+    pub count: usize,
+    pub words: usize,
+    /// 内置词表 `eff_large`（默认，7776 词）或 `eff_short`（1296 词）
+    pub wordlist: String,
+    /// 自定义词表文件，每行一个词，兼容 `11111<TAB>word` 的骰子编号格式
+    pub wordlist_path: Option<String>,
+    pub separator: String,
+    /// `none`、`first`（首字母大写）、`upper`、`random`（每个词随机首字母大写）
+    pub capitalize: String,
+    /// 在随机一个词后追加一位数字
+    pub digit: bool,
+    /// 在随机一个词后追加一个符号
+    pub symbol: bool,
+}
+
+impl Default for PassphraseOptions {
+    fn default() -> Self {
+        PassphraseOptions {
+            count: 1,
+            words: 6,
+            wordlist: "eff_large".to_string(),
+            wordlist_path: None,
+            separator: " ".to_string(),
+            capitalize: "none".to_string(),
+            digit: false,
+            symbol: false,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct Passphrase {
+    pub passphrase: String,
+    /// 熵（bit），按词表大小和各项随机选择计算
+    pub entropy: f64,
+}
+
+/// Diceware 口令生成
+#[tauri::command]
+pub async fn gen_passphrases(
+    options: Option<PassphraseOptions>,
+    seed: Option<u64>,
+) -> Result<Vec<Passphrase>, String> {
+    let options = options.unwrap_or_default();
+    if options.count == 0 {
+        return Err("count must be at least 1".to_string());
+    }
+    if options.words == 0 {
+        return Err("word count must be at least 1".to_string());
+    }
+
+    let content = match &options.wordlist_path {
+        Some(path) if !path.is_empty() => {
+            std::fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?
+        }
+        _ => match options.wordlist.as_str() {
+            "" | "eff_large" => EFF_LARGE.to_string(),
+            "eff_short" => EFF_SHORT.to_string(),
+            other => return Err(format!("unknown word list: {other}")),
+        },
+    };
+    let wordlist = parse_wordlist(&content);
+    if wordlist.len() < 2 {
+        return Err("word list must contain at least 2 distinct words".to_string());
+    }
+
+    let capitalize = options.capitalize.as_str();
+    if !matches!(capitalize, "" | "none" | "first" | "upper" | "random") {
+        return Err(format!("unknown capitalization: {capitalize}"));
+    }
+
+    let words = options.words as f64;
+    let mut entropy = words * (wordlist.len() as f64).log2();
+    if capitalize == "random" {
+        entropy += words;
+    }
+    if options.digit {
+        entropy += 10f64.log2() + words.log2();
+    }
+    if options.symbol {
+        entropy += (SYMBOLS.len() as f64).log2() + words.log2();
+    }
+
+    let mut rng = Source::new(seed);
+    let mut result = vec![];
+    for _ in 0..options.count {
+        let mut words = (0..options.words)
+            .map(|_| {
+                let word = *wordlist.choose(&mut rng).expect("word list is not empty");
+                match capitalize {
+                    "first" => capitalize_first(word),
+                    "upper" => word.to_uppercase(),
+                    "random" if rng.gen_bool(0.5) => capitalize_first(word),
+                    _ => word.to_string(),
+                }
+            })
+            .collect::<Vec<_>>();
+        if options.digit {
+            let n = rng.gen_range(0..words.len());
+            words[n].push(char::from(b'0' + rng.gen_range(0..10)));
+        }
+        if options.symbol {
+            let n = rng.gen_range(0..words.len());
+            words[n].push(char::from(*SYMBOLS.choose(&mut rng).expect("symbols")));
+        }
+
+        result.push(Passphrase {
+            passphrase: words.join(&options.separator),
+            entropy,
+        });
+    }
+
+    Ok(result)
+}
+
+/// 去掉骰子编号，去重后保留原顺序
+fn parse_wordlist(content: &str) -> Vec<&str> {
+    let mut words = vec![];
+    let mut seen = std::collections::HashSet::new();
+    for line in content.lines() {
+        let line = line.trim();
+        let word = match line.split_once(char::is_whitespace) {
+            Some((dice, word)) if dice.chars().all(|c| c.is_ascii_digit()) => word.trim(),
+            _ => line,
+        };
+        if !word.is_empty() && seen.insert(word) {
+            words.push(word);
+        }
+    }
+    words
+}
+
+fn capitalize_first(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -1,36 +1,1039 @@
-use passwords::PasswordGenerator;
-use uuid::Uuid;
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
 
+use rand::rngs::OsRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use serde::{Deserialize, Serialize};
+use uuid::{Builder, Uuid};
+
+/// 与 passwords crate 相同的 ASCII 标点
+const SYMBOLS: &str = "!\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~";
+/// `exclude_similar` 排除的易混淆字符
+const SIMILAR: &str = "0O1lIio|`'\"";
+/// 无法满足“不连续重复”时的最大重试次数
+const MAX_ATTEMPTS: usize = 100;
+
+/// ULID 使用的 Crockford Base32
+const CROCKFORD: &[u8] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const BASE62: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+const NANOID_ALPHABET: &str = "_-0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+/// KSUID 的纪元 2014-05-13T16:53:20Z
+const KSUID_EPOCH: i64 = 1_400_000_000;
+
+/// 单调 ULID 上一次的时间戳和随机部分
+static LAST_ULID: Mutex<(u64, u128)> = Mutex::new((0, 0));
+/// Snowflake 上一次的时间戳和序列号
+static LAST_SNOWFLAKE: Mutex<(u64, u64)> = Mutex::new((0, 0));
+/// 指定种子时基于时间的 ID 使用的固定时间 2024-01-01T00:00:00Z
+const SEEDED_TIME_MS: u64 = 1_704_067_200_000;
+
+/// 生成器的随机源：默认为操作系统 CSPRNG，指定种子时为 ChaCha20，每次运行结果相同
+pub enum Source {
+    Os(OsRng),
+    Seeded(Box<ChaCha20Rng>),
+}
+
+impl Source {
+    pub fn new(seed: Option<u64>) -> Self {
+        match seed {
+            Some(seed) => Source::Seeded(Box::new(ChaCha20Rng::seed_from_u64(seed))),
+            None => Source::Os(OsRng),
+        }
+    }
+
+    pub fn is_seeded(&self) -> bool {
+        matches!(self, Source::Seeded(_))
+    }
+
+    /// 当前 Unix 毫秒时间，指定种子时固定为 `SEEDED_TIME_MS` 以便复现
+    pub fn now_ms(&self) -> u64 {
+        match self {
+            Source::Os(_) => SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0),
+            Source::Seeded(_) => SEEDED_TIME_MS,
+        }
+    }
+}
+
+// 两种随机源都是 CSPRNG，可以用于生成密钥
+impl rand::CryptoRng for Source {}
+
+impl RngCore for Source {
+    fn next_u32(&mut self) -> u32 {
+        match self {
+            Source::Os(rng) => rng.next_u32(),
+            Source::Seeded(rng) => rng.next_u32(),
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match self {
+            Source::Os(rng) => rng.next_u64(),
+            Source::Seeded(rng) => rng.next_u64(),
+        }
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        match self {
+            Source::Os(rng) => rng.fill_bytes(dest),
+            Source::Seeded(rng) => rng.fill_bytes(dest),
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        match self {
+            Source::Os(rng) => rng.try_fill_bytes(dest),
+            Source::Seeded(rng) => rng.try_fill_bytes(dest),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct PasswordOptions {
+    pub length: usize,
+    pub numbers: bool,
+    pub loweralpha: bool,
+    pub upperalpha: bool,
+    pub symbols: bool,
+    pub spaces: bool,
+    /// 每个启用的字符类至少出现一次
+    pub strict: bool,
+    /// 自定义符号集，替换默认的 ASCII 标点
+    pub symbol_set: Option<String>,
+    /// 不使用的字符
+    pub exclude: String,
+    /// 排除 `0O1lIio|` 等易混淆字符
+    pub exclude_similar: bool,
+    pub min_numbers: usize,
+    pub min_lowercase: usize,
+    pub min_uppercase: usize,
+    pub min_symbols: usize,
+    /// 首字符必须是字母
+    pub start_with_letter: bool,
+    /// 相邻字符不相同
+    pub no_consecutive: bool,
+    /// 每个字符最多出现一次
+    pub no_repeat: bool,
+}
+
+impl Default for PasswordOptions {
+    fn default() -> Self {
+        PasswordOptions {
+            length: 16,
+            numbers: true,
+            loweralpha: true,
+            upperalpha: true,
+            symbols: false,
+            spaces: false,
+            strict: false,
+            symbol_set: None,
+            exclude: String::new(),
+            exclude_similar: false,
+            min_numbers: 0,
+            min_lowercase: 0,
+            min_uppercase: 0,
+            min_symbols: 0,
+            start_with_letter: false,
+            no_consecutive: false,
+            no_repeat: false,
+        }
+    }
+}
+
+struct CharClass {
+    name: &'static str,
+    chars: Vec<char>,
+    min: usize,
+}
+
+/// 按规则生成随机密码，规则互相矛盾时返回错误
 #[tauri::command]
 pub async fn gen_passwords(
     count: usize,
-    length: usize,
-    numbers: bool,
-    loweralpha: bool,
-    upperalpha: bool,
-    symbols: bool,
-    strict: bool,
+    options: Option<PasswordOptions>,
+    seed: Option<u64>,
+) -> Result<Vec<String>, String> {
+    let options = options.unwrap_or_default();
+    let classes = char_classes(&options)?;
+    check_rules(&options, &classes)?;
+
+    let mut rng = Source::new(seed);
+    (0..count)
+        .map(|_| generate(&options, &classes, &mut rng))
+        .collect()
+}
+
+fn char_classes(options: &PasswordOptions) -> Result<Vec<CharClass>, String> {
+    let excluded =
+        |c: char| options.exclude.contains(c) || (options.exclude_similar && SIMILAR.contains(c));
+
+    let symbols = match options.symbol_set.as_deref() {
+        None | Some("") => SYMBOLS,
+        Some(set) => {
+            if let Some(c) = set
+                .chars()
+                .find(|c| c.is_alphanumeric() || c.is_whitespace() || c.is_control())
+            {
+                return Err(format!(
+                    "symbol set must only contain punctuation, found {c:?}"
+                ));
+            }
+            set
+        }
+    };
+
+    let specs = [
+        (
+            "numbers",
+            options.numbers,
+            "0123456789",
+            options.min_numbers,
+        ),
+        (
+            "lowercase letters",
+            options.loweralpha,
+            "abcdefghijklmnopqrstuvwxyz",
+            options.min_lowercase,
+        ),
+        (
+            "uppercase letters",
+            options.upperalpha,
+            "ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+            options.min_uppercase,
+        ),
+        ("symbols", options.symbols, symbols, options.min_symbols),
+        ("spaces", options.spaces, " ", 0),
+    ];
+
+    let mut classes = vec![];
+    for (name, enabled, chars, min) in specs {
+        if !enabled {
+            if min > 0 {
+                return Err(format!(
+                    "at least {min} {name} required, but {name} are disabled"
+                ));
+            }
+            continue;
+        }
+        let mut chars = chars.chars().filter(|&c| !excluded(c)).collect::<Vec<_>>();
+        chars.sort_unstable();
+        chars.dedup();
+        if chars.is_empty() {
+            return Err(format!("no {name} left after exclusions"));
+        }
+        let min = if options.strict && name != "spaces" {
+            min.max(1)
+        } else {
+            min
+        };
+        classes.push(CharClass { name, chars, min });
+    }
+
+    if classes.is_empty() {
+        return Err("no character class enabled".to_string());
+    }
+    Ok(classes)
+}
+
+/// 生成前检查规则能否同时满足
+fn check_rules(options: &PasswordOptions, classes: &[CharClass]) -> Result<(), String> {
+    let length = options.length;
+    if length == 0 {
+        return Err("password length must be at least 1".to_string());
+    }
+
+    let letter_min = classes
+        .iter()
+        .filter(|c| is_letters(c))
+        .map(|c| c.min)
+        .sum::<usize>();
+    if options.start_with_letter && !classes.iter().any(is_letters) {
+        return Err("password must start with a letter, but letters are disabled".to_string());
+    }
+    let required = classes.iter().map(|c| c.min).sum::<usize>()
+        + usize::from(options.start_with_letter && letter_min == 0);
+    if required > length {
+        return Err(format!(
+            "rules require at least {required} characters, but the length is {length}"
+        ));
+    }
+
+    if options.no_repeat {
+        let available = classes.iter().map(|c| c.chars.len()).sum::<usize>();
+        if available < length {
+            return Err(format!(
+                "only {available} distinct characters available, cannot build {length} characters without repeats"
+            ));
+        }
+        if let Some(class) = classes.iter().find(|c| c.min > c.chars.len()) {
+            return Err(format!(
+                "at least {} {} required without repeats, but only {} available",
+                class.min,
+                class.name,
+                class.chars.len()
+            ));
+        }
+    }
+
+    if options.no_consecutive {
+        let available = classes.iter().map(|c| c.chars.len()).sum::<usize>();
+        if available == 1 && length > 1 {
+            return Err(
+                "only one character available, cannot avoid consecutive repeats".to_string(),
+            );
+        }
+        // 只有一个字符的类，最少次数超过一半长度时必然相邻
+        if let Some(class) = classes
+            .iter()
+            .find(|c| c.chars.len() == 1 && c.min > length.div_ceil(2))
+        {
+            return Err(format!(
+                "{} {:?} is required {} times, it cannot avoid consecutive repeats in {length} characters",
+                class.name, class.chars[0], class.min
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn generate(
+    options: &PasswordOptions,
+    classes: &[CharClass],
+    rng: &mut impl Rng,
+) -> Result<String, String> {
+    let letters = classes
+        .iter()
+        .filter(|c| is_letters(c))
+        .flat_map(|c| c.chars.iter().copied())
+        .collect::<Vec<_>>();
+    let pool = classes
+        .iter()
+        .flat_map(|c| c.chars.iter().copied())
+        .collect::<Vec<_>>();
+
+    for _ in 0..MAX_ATTEMPTS {
+        let mut used = HashSet::new();
+        let mut password = vec![];
+        let mut pick = |chars: &[char], n: usize, password: &mut Vec<char>| {
+            if options.no_repeat {
+                let free = chars
+                    .iter()
+                    .filter(|c| !used.contains(*c))
+                    .copied()
+                    .collect::<Vec<_>>();
+                for &c in free.choose_multiple(rng, n) {
+                    used.insert(c);
+                    password.push(c);
+                }
+            } else {
+                for _ in 0..n {
+                    password.push(*chars.choose(rng).expect("class is not empty"));
+                }
+            }
+        };
+
+        for class in classes {
+            pick(&class.chars, class.min, &mut password);
+        }
+        if options.start_with_letter && !password.iter().any(|c| letters.contains(c)) {
+            pick(&letters, 1, &mut password);
+        }
+        let rest = options.length - password.len();
+        pick(&pool, rest, &mut password);
+
+        password.shuffle(rng);
+        if options.start_with_letter && !letters.contains(&password[0]) {
+            let i = password
+                .iter()
+                .position(|c| letters.contains(c))
+                .expect("a letter was picked");
+            password.swap(0, i);
+        }
+        if options.no_consecutive && !separate_repeats(&mut password, &letters, options, rng) {
+            continue;
+        }
+        return Ok(password.into_iter().collect());
+    }
+
+    Err("could not avoid consecutive repeats with these rules, allow more characters or lower the minimums".to_string())
+}
+
+/// 交换位置消除相邻的相同字符，失败时返回 false
+fn separate_repeats(
+    password: &mut [char],
+    letters: &[char],
+    options: &PasswordOptions,
+    rng: &mut impl Rng,
+) -> bool {
+    let valid_at = |p: &[char], k: usize| {
+        (k == 0 || p[k] != p[k - 1])
+            && (k + 1 == p.len() || p[k] != p[k + 1])
+            && (k != 0 || !options.start_with_letter || letters.contains(&p[0]))
+    };
+
+    let mut positions = (0..password.len()).collect::<Vec<_>>();
+    for i in 1..password.len() {
+        if password[i] != password[i - 1] {
+            continue;
+        }
+        positions.shuffle(rng);
+        let swapped = positions.iter().any(|&j| {
+            password.swap(i, j);
+            if valid_at(password, i) && valid_at(password, j) {
+                return true;
+            }
+            password.swap(i, j);
+            false
+        });
+        if !swapped {
+            return false;
+        }
+    }
+    true
+}
+
+fn is_letters(class: &CharClass) -> bool {
+    class.name.ends_with("letters")
+}
+
+#[tauri::command]
+pub fn gen_uuid_v4(seed: Option<u64>) -> String {
+    new_v4(&mut Source::new(seed)).into()
+}
+
+#[tauri::command]
+pub fn gen_uuid_v7(seed: Option<u64>) -> String {
+    new_v7(&mut Source::new(seed)).into()
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct UuidOptions {
+    /// 1、3、4、5、6、7、8
+    pub version: u8,
+    /// v3/v5 以外的版本生成的个数
+    pub count: usize,
+    /// `hyphenated`、`simple`、`braced`、`urn`
+    pub format: String,
+    pub uppercase: bool,
+    /// v3/v5 的命名空间：`dns`、`url`、`oid`、`x500` 或任意 UUID
+    pub namespace: String,
+    /// v3/v5 的名称，每个名称生成一个 UUID
+    pub names: Vec<String>,
+    /// v1/v6 的节点 ID（MAC 地址），为空时随机生成
+    pub node: Option<String>,
+    /// v8 的 16 字节自定义数据（十六进制），为空时随机生成
+    pub data: Option<String>,
+}
+
+impl Default for UuidOptions {
+    fn default() -> Self {
+        UuidOptions {
+            version: 4,
+            count: 1,
+            format: "hyphenated".to_string(),
+            uppercase: false,
+            namespace: "dns".to_string(),
+            names: vec![],
+            node: None,
+            data: None,
+        }
+    }
+}
+
+/// 批量生成任意版本的 UUID
+#[tauri::command]
+pub fn gen_uuids(options: Option<UuidOptions>, seed: Option<u64>) -> Result<Vec<String>, String> {
+    let options = options.unwrap_or_default();
+    let mut rng = Source::new(seed);
+    if !matches!(
+        options.format.as_str(),
+        "hyphenated" | "simple" | "braced" | "urn"
+    ) {
+        return Err(format!("unknown UUID format: {}", options.format));
+    }
+
+    let uuids = match options.version {
+        1 | 6 => {
+            let node = match options.node.as_deref() {
+                None | Some("") => random_node(&mut rng),
+                Some(node) => parse_node(node)?,
+            };
+            // 指定种子时时间固定，每个 UUID 前进一个 100ns 刻度以保证不重复
+            let start = rng.now_ms() * 10_000 + 0x01B2_1DD2_1381_4000;
+            let clock_sequence = rng.gen::<u16>() & 0x3fff;
+            (0..options.count as u64)
+                .map(|i| match (options.version, rng.is_seeded()) {
+                    (1, false) => Uuid::now_v1(&node),
+                    (_, false) => Uuid::now_v6(&node),
+                    (version, true) => {
+                        let ticks = start + i;
+                        match version {
+                            1 => Builder::from_rfc4122_timestamp(ticks, clock_sequence, &node),
+                            _ => {
+                                Builder::from_sorted_rfc4122_timestamp(ticks, clock_sequence, &node)
+                            }
+                        }
+                        .into_uuid()
+                    }
+                })
+                .collect::<Vec<_>>()
+        }
+        3 | 5 => {
+            if options.names.is_empty() {
+                return Err(format!(
+                    "UUID v{} requires at least one name",
+                    options.version
+                ));
+            }
+            let namespace = parse_namespace(&options.namespace)?;
+            options
+                .names
+                .iter()
+                .map(|name| match options.version {
+                    3 => Uuid::new_v3(&namespace, name.as_bytes()),
+                    _ => Uuid::new_v5(&namespace, name.as_bytes()),
+                })
+                .collect()
+        }
+        4 => (0..options.count).map(|_| new_v4(&mut rng)).collect(),
+        7 => (0..options.count).map(|_| new_v7(&mut rng)).collect(),
+        8 => {
+            let data = match options.data.as_deref() {
+                None | Some("") => None,
+                Some(data) => Some(parse_v8_data(data)?),
+            };
+            (0..options.count)
+                .map(|_| Uuid::new_v8(data.unwrap_or_else(|| rng.gen())))
+                .collect()
+        }
+        version => return Err(format!("unsupported UUID version: {version}")),
+    };
+
+    Ok(uuids
+        .into_iter()
+        .map(|uuid| format_uuid(&uuid, &options.format, options.uppercase))
+        .collect())
+}
+
+#[derive(Serialize, Debug)]
+pub struct UuidInfo {
+    pub hyphenated: String,
+    pub version: usize,
+    pub version_name: &'static str,
+    pub variant: &'static str,
+    /// v1/v6/v7 内嵌的时间（UTC）
+    pub timestamp: Option<String>,
+    pub unix_ms: Option<i64>,
+    /// v1/v6 的时钟序列
+    pub clock_sequence: Option<u16>,
+    /// v1/v6 的节点 ID
+    pub node: Option<String>,
+}
+
+/// 解析任意格式的 UUID，报告版本、变体以及内嵌的时间和节点
+#[tauri::command]
+pub fn inspect_uuid(uuid: &str) -> Result<UuidInfo, String> {
+    let uuid = Uuid::parse_str(uuid.trim()).map_err(|e| e.to_string())?;
+    let bytes = uuid.as_bytes();
+
+    let version = uuid.get_version_num();
+    let version_name = if uuid.is_nil() {
+        "nil"
+    } else if uuid.is_max() {
+        "max"
+    } else {
+        match version {
+            1 => "time-based",
+            2 => "DCE security",
+            3 => "name-based (MD5)",
+            4 => "random",
+            5 => "name-based (SHA-1)",
+            6 => "reordered time-based",
+            7 => "Unix time-based",
+            8 => "custom",
+            _ => "unknown",
+        }
+    };
+    let variant = match uuid.get_variant() {
+        uuid::Variant::NCS => "NCS",
+        uuid::Variant::RFC4122 => "RFC 4122",
+        uuid::Variant::Microsoft => "Microsoft",
+        _ => "future",
+    };
+
+    let mut info = UuidInfo {
+        hyphenated: uuid.hyphenated().to_string(),
+        version,
+        version_name,
+        variant,
+        timestamp: None,
+        unix_ms: None,
+        clock_sequence: None,
+        node: None,
+    };
+    if uuid.get_variant() != uuid::Variant::RFC4122 {
+        return Ok(info);
+    }
+
+    let field = |range: std::ops::Range<usize>| {
+        bytes[range]
+            .iter()
+            .fold(0i64, |n, &b| n << 8 | i64::from(b))
+    };
+    // v1/v6 以 100 纳秒为单位，从 1582-10-15 起算
+    let ticks = match version {
+        1 => Some((field(6..8) & 0x0fff) << 48 | field(4..6) << 32 | field(0..4)),
+        6 => Some(field(0..4) << 28 | field(4..6) << 12 | (field(6..8) & 0x0fff)),
+        _ => None,
+    };
+    let unix_ms = match ticks {
+        Some(ticks) => {
+            info.clock_sequence = Some(field(8..10) as u16 & 0x3fff);
+            info.node = Some(format_node(&bytes[10..]));
+            Some((ticks - 0x01B2_1DD2_1381_4000).div_euclid(10_000))
+        }
+        None if version == 7 => Some(field(0..6)),
+        None => None,
+    };
+    info.timestamp = unix_ms.map(format_unix_ms);
+    info.unix_ms = unix_ms;
+    Ok(info)
+}
+
+fn new_v4(rng: &mut Source) -> Uuid {
+    Builder::from_random_bytes(rng.gen()).into_uuid()
+}
+
+/// 系统随机时交给 uuid crate，保证同一毫秒内的计数器递增
+fn new_v7(rng: &mut Source) -> Uuid {
+    match rng {
+        Source::Os(_) => Uuid::now_v7(),
+        Source::Seeded(_) => {
+            Builder::from_unix_timestamp_millis(rng.now_ms(), &rng.gen()).into_uuid()
+        }
+    }
+}
+
+fn format_uuid(uuid: &Uuid, format: &str, uppercase: bool) -> String {
+    let s = match format {
+        "simple" => uuid.simple().to_string(),
+        "braced" => uuid.braced().to_string(),
+        "urn" => uuid.urn().to_string(),
+        _ => uuid.hyphenated().to_string(),
+    };
+    if !uppercase {
+        return s;
+    }
+    // 只大写十六进制部分，`urn:uuid:` 前缀按 RFC 保持小写
+    match s.strip_prefix("urn:uuid:") {
+        Some(hex) => format!("urn:uuid:{}", hex.to_uppercase()),
+        None => s.to_uppercase(),
+    }
+}
+
+fn parse_namespace(namespace: &str) -> Result<Uuid, String> {
+    match namespace.to_lowercase().as_str() {
+        "dns" => Ok(Uuid::NAMESPACE_DNS),
+        "url" => Ok(Uuid::NAMESPACE_URL),
+        "oid" => Ok(Uuid::NAMESPACE_OID),
+        "x500" => Ok(Uuid::NAMESPACE_X500),
+        other => Uuid::parse_str(other).map_err(|e| format!("invalid namespace {namespace}: {e}")),
+    }
+}
+
+/// `01:23:45:67:89:ab`、`01-23-45-67-89-ab` 或 `0123456789ab`
+fn parse_node(node: &str) -> Result<[u8; 6], String> {
+    let hex = node.replace([':', '-'], "");
+    let bytes = decode_hex(&hex).filter(|b| b.len() == 6);
+    bytes
+        .map(|b| b.try_into().expect("6 bytes"))
+        .ok_or_else(|| format!("node ID must be a 6-byte MAC address: {node}"))
+}
+
+fn parse_v8_data(data: &str) -> Result<[u8; 16], String> {
+    let hex = data.replace('-', "");
+    decode_hex(&hex)
+        .filter(|b| b.len() == 16)
+        .map(|b| b.try_into().expect("16 bytes"))
+        .ok_or_else(|| format!("v8 data must be 16 bytes of hex: {data}"))
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| match pair {
+            [hi, lo] => Some((hex_digit(*hi)? << 4) | hex_digit(*lo)?),
+            _ => None,
+        })
+        .collect()
+}
+
+fn hex_digit(c: u8) -> Option<u8> {
+    (c as char).to_digit(16).map(|d| d as u8)
+}
+
+/// 随机节点 ID，按 RFC 4122 设置多播位以区别于真实 MAC 地址
+fn random_node(rng: &mut Source) -> [u8; 6] {
+    let mut node: [u8; 6] = rng.gen();
+    node[0] |= 0x01;
+    node
+}
+
+fn format_node(node: &[u8]) -> String {
+    node.iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// Unix 毫秒时间戳转为 `2006-01-02T15:04:05.000Z`
+fn format_unix_ms(ms: i64) -> String {
+    let secs = ms.div_euclid(1000);
+    let days = secs.div_euclid(86400);
+    let rem = secs.rem_euclid(86400);
+
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60,
+        ms.rem_euclid(1000)
+    )
+}
+
+/// 生成 ULID，`monotonic` 时同一毫秒内随机部分递增，保证严格有序
+#[tauri::command]
+pub fn gen_ulids(
+    count: usize,
+    monotonic: Option<bool>,
+    seed: Option<u64>,
 ) -> Result<Vec<String>, String> {
-    let pg = PasswordGenerator {
-        length,
-        numbers,
-        lowercase_letters: loweralpha,
-        uppercase_letters: upperalpha,
-        symbols,
-        strict,
-        spaces: false,
-        exclude_similar_characters: false,
+    let mut rng = Source::new(seed);
+    // 指定种子时不与之前的调用共享状态
+    let mut global = LAST_ULID.lock().map_err(|e| e.to_string())?;
+    let mut local = (0, 0);
+    let last = if rng.is_seeded() {
+        &mut local
+    } else {
+        &mut *global
     };
+    let mut ids = vec![];
+    for _ in 0..count {
+        let now = rng.now_ms();
+        let (ms, random) = if monotonic.unwrap_or(false) && now <= last.0 {
+            let random = last.1 + 1;
+            if random >> 80 != 0 {
+                return Err("ULID random component overflowed within one millisecond".to_string());
+            }
+            (last.0, random)
+        } else {
+            (now, rng.gen::<u128>() >> 48)
+        };
+        *last = (ms, random);
+        ids.push(encode_ulid(u128::from(ms) << 80 | random));
+    }
+    Ok(ids)
+}
 
-    pg.generate(count).map_err(|e| e.into())
+/// 生成 KSUID：4 字节秒级时间戳加 16 字节随机数，Base62 编码为 27 个字符
+#[tauri::command]
+pub fn gen_ksuids(count: usize, seed: Option<u64>) -> Vec<String> {
+    let mut rng = Source::new(seed);
+    (0..count)
+        .map(|_| {
+            let mut bytes = [0u8; 20];
+            let seconds = (rng.now_ms() / 1000) as i64 - KSUID_EPOCH;
+            bytes[..4].copy_from_slice(&(seconds as u32).to_be_bytes());
+            rng.fill(&mut bytes[4..]);
+            encode_base62(&bytes, 27)
+        })
+        .collect()
 }
 
+/// 生成 NanoID，默认 21 个字符，字母表为 `A-Za-z0-9_-`
 #[tauri::command]
-pub fn gen_uuid_v4() -> String {
-    Uuid::new_v4().into()
+pub fn gen_nanoids(
+    count: usize,
+    alphabet: Option<String>,
+    size: Option<usize>,
+    seed: Option<u64>,
+) -> Result<Vec<String>, String> {
+    let alphabet = match alphabet.as_deref() {
+        None | Some("") => NANOID_ALPHABET.chars().collect::<Vec<_>>(),
+        Some(alphabet) => {
+            let chars = alphabet.chars().collect::<Vec<_>>();
+            if chars.len() < 2 || chars.len() > 256 {
+                return Err("alphabet must have between 2 and 256 characters".to_string());
+            }
+            if chars.iter().collect::<HashSet<_>>().len() != chars.len() {
+                return Err("alphabet must not contain duplicate characters".to_string());
+            }
+            chars
+        }
+    };
+    let size = size.unwrap_or(21);
+    if size == 0 {
+        return Err("size must be at least 1".to_string());
+    }
+
+    let mut rng = Source::new(seed);
+    Ok((0..count)
+        .map(|_| {
+            (0..size)
+                .map(|_| *alphabet.choose(&mut rng).expect("alphabet is not empty"))
+                .collect()
+        })
+        .collect())
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct SnowflakeLayout {
+    /// 纪元（Unix 毫秒），默认 Twitter 的 2010-11-04T01:42:54.657Z
+    pub epoch: u64,
+    pub worker_id: u64,
+    pub worker_bits: u32,
+    pub sequence_bits: u32,
 }
 
+impl Default for SnowflakeLayout {
+    fn default() -> Self {
+        SnowflakeLayout {
+            epoch: 1_288_834_974_657,
+            worker_id: 0,
+            worker_bits: 10,
+            sequence_bits: 12,
+        }
+    }
+}
+
+impl SnowflakeLayout {
+    /// 时间戳占用的位数，最高位保留为 0
+    fn timestamp_bits(&self) -> Result<u32, String> {
+        match self
+            .worker_bits
+            .checked_add(self.sequence_bits)
+            .and_then(|bits| 63u32.checked_sub(bits))
+        {
+            Some(bits) if bits >= 32 => Ok(bits),
+            _ => Err(format!(
+                "worker and sequence bits ({} + {}) leave less than 32 bits for the timestamp",
+                self.worker_bits, self.sequence_bits
+            )),
+        }
+    }
+}
+
+/// 生成 Snowflake ID，以字符串返回避免 JS 数字丢失精度
+///
+/// 同一毫秒内序列号用尽时借用下一毫秒，不会等待时钟。
 #[tauri::command]
-pub fn gen_uuid_v7() -> String {
-    Uuid::now_v7().into()
+pub fn gen_snowflakes(
+    count: usize,
+    layout: Option<SnowflakeLayout>,
+    seed: Option<u64>,
+) -> Result<Vec<String>, String> {
+    let layout = layout.unwrap_or_default();
+    let timestamp_bits = layout.timestamp_bits()?;
+    if layout.worker_id >> layout.worker_bits != 0 {
+        return Err(format!(
+            "worker ID {} does not fit in {} bits",
+            layout.worker_id, layout.worker_bits
+        ));
+    }
+
+    // Snowflake 没有随机部分，种子只用于固定时间
+    let source = Source::new(seed);
+    let mut global = LAST_SNOWFLAKE.lock().map_err(|e| e.to_string())?;
+    let mut local = (0, 0);
+    let last = if source.is_seeded() {
+        &mut local
+    } else {
+        &mut *global
+    };
+    let mut ids = vec![];
+    for _ in 0..count {
+        let now = source.now_ms();
+        let (mut ms, mut sequence) = (now.max(last.0), 0);
+        if ms == last.0 {
+            sequence = last.1 + 1;
+            if sequence >> layout.sequence_bits != 0 {
+                ms += 1;
+                sequence = 0;
+            }
+        }
+        *last = (ms, sequence);
+
+        let elapsed = ms
+            .checked_sub(layout.epoch)
+            .ok_or("the epoch is in the future")?;
+        if elapsed >> timestamp_bits != 0 {
+            return Err(format!("timestamp does not fit in {timestamp_bits} bits"));
+        }
+        let id = elapsed << (layout.worker_bits + layout.sequence_bits)
+            | layout.worker_id << layout.sequence_bits
+            | sequence;
+        ids.push(id.to_string());
+    }
+    Ok(ids)
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct IdInfo {
+    pub timestamp: Option<String>,
+    pub unix_ms: Option<i64>,
+    /// ULID、KSUID 的随机部分（十六进制）
+    pub random: Option<String>,
+    pub worker_id: Option<u64>,
+    pub sequence: Option<u64>,
+}
+
+/// 从 ULID、KSUID、Snowflake ID 中解出时间戳和其他组成部分
+#[tauri::command]
+pub fn decode_id(kind: &str, id: &str, layout: Option<SnowflakeLayout>) -> Result<IdInfo, String> {
+    let id = id.trim();
+    let mut info = IdInfo::default();
+    let unix_ms = match kind {
+        "ulid" => {
+            let value = decode_ulid(id)?;
+            info.random = Some(format!("{:020x}", value & ((1 << 80) - 1)));
+            (value >> 80) as i64
+        }
+        "ksuid" => {
+            if id.len() != 27 {
+                return Err(format!("KSUID must be 27 characters, found {}", id.len()));
+            }
+            let bytes = decode_base62(id, 20)?;
+            info.random = Some(bytes[4..].iter().map(|b| format!("{b:02x}")).collect());
+            let seconds = u32::from_be_bytes(bytes[..4].try_into().expect("4 bytes"));
+            (i64::from(seconds) + KSUID_EPOCH) * 1000
+        }
+        "snowflake" => {
+            let layout = layout.unwrap_or_default();
+            layout.timestamp_bits()?;
+            let value = id
+                .parse::<u64>()
+                .map_err(|e| format!("invalid Snowflake ID {id}: {e}"))?;
+            let mask = |bits: u32| (1u64 << bits) - 1;
+            info.sequence = Some(value & mask(layout.sequence_bits));
+            info.worker_id = Some(value >> layout.sequence_bits & mask(layout.worker_bits));
+            let elapsed = value >> (layout.worker_bits + layout.sequence_bits);
+            elapsed
+                .checked_add(layout.epoch)
+                .and_then(|ms| i64::try_from(ms).ok())
+                .ok_or("timestamp is out of range")?
+        }
+        "nanoid" => return Err("NanoID is fully random and carries no embedded data".to_string()),
+        other => return Err(format!("unknown ID kind: {other}")),
+    };
+    info.timestamp = Some(format_unix_ms(unix_ms));
+    info.unix_ms = Some(unix_ms);
+    Ok(info)
+}
+
+fn encode_ulid(value: u128) -> String {
+    (0..26)
+        .rev()
+        .map(|i| CROCKFORD[(value >> (i * 5)) as usize & 0x1f] as char)
+        .collect()
+}
+
+/// 不区分大小写，按 Crockford 规则把 `I`、`L` 视为 `1`，`O` 视为 `0`
+fn decode_ulid(id: &str) -> Result<u128, String> {
+    if id.len() != 26 {
+        return Err(format!("ULID must be 26 characters, found {}", id.len()));
+    }
+    let mut value = 0u128;
+    for c in id.chars() {
+        let c = match c.to_ascii_uppercase() {
+            'I' | 'L' => '1',
+            'O' => '0',
+            c => c,
+        };
+        let digit = CROCKFORD
+            .iter()
+            .position(|&d| d as char == c)
+            .ok_or_else(|| format!("invalid ULID character {c:?}"))?;
+        value = value
+            .checked_mul(32)
+            .map(|v| v | digit as u128)
+            .ok_or("ULID is out of range")?;
+    }
+    Ok(value)
+}
+
+/// 大端字节按 62 进制编码，左侧以 `0` 补齐到 `width`
+fn encode_base62(bytes: &[u8], width: usize) -> String {
+    let mut number = bytes.to_vec();
+    let mut digits = vec![];
+    while number.iter().any(|&b| b != 0) {
+        let mut remainder = 0u32;
+        for b in number.iter_mut() {
+            let acc = remainder << 8 | u32::from(*b);
+            *b = (acc / 62) as u8;
+            remainder = acc % 62;
+        }
+        digits.push(BASE62[remainder as usize]);
+    }
+    digits.resize(digits.len().max(width), b'0');
+    digits.iter().rev().map(|&d| d as char).collect()
+}
+
+fn decode_base62(id: &str, len: usize) -> Result<Vec<u8>, String> {
+    let mut bytes = vec![0u8; len];
+    for c in id.chars() {
+        let digit = BASE62
+            .iter()
+            .position(|&d| d as char == c)
+            .ok_or_else(|| format!("invalid base62 character {c:?}"))?;
+        let mut carry = digit as u32;
+        for b in bytes.iter_mut().rev() {
+            let acc = u32::from(*b) * 62 + carry;
+            *b = acc as u8;
+            carry = acc >> 8;
+        }
+        if carry != 0 {
+            return Err(format!("{id} does not fit in {len} bytes"));
+        }
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeded_time_based_uuids_are_unique() {
+        for version in [1, 6] {
+            let options = || UuidOptions {
+                version,
+                count: 10_000,
+                ..Default::default()
+            };
+            let uuids = gen_uuids(Some(options()), Some(42)).unwrap();
+            let unique = uuids.iter().collect::<std::collections::HashSet<_>>();
+            assert_eq!(unique.len(), uuids.len(), "v{version}");
+            assert_eq!(uuids, gen_uuids(Some(options()), Some(42)).unwrap());
+        }
+    }
 }
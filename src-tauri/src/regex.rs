@@ -1,25 +1,617 @@
-use regex::Regex;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
 
+use lru::LruCache;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+/// 对应 `RegexBuilder` 的选项，省略的字段使用 regex 的默认值
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(default)]
+pub struct RegexFlags {
+    pub case_insensitive: bool,
+    pub multi_line: bool,
+    pub dot_matches_new_line: bool,
+    pub ignore_whitespace: bool,
+    pub swap_greed: bool,
+    pub unicode: bool,
+    /// 编译后程序的大小上限（字节）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size_limit: Option<usize>,
+    /// 惰性 DFA 缓存的大小上限（字节）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dfa_size_limit: Option<usize>,
+    /// `std`（默认）、`fancy`（支持环视和反向引用）、`auto`（标准引擎不支持时改用 fancy）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub engine: Option<String>,
+    /// fancy 引擎的回溯步数上限，默认 1,000,000
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backtrack_limit: Option<usize>,
+}
+
+impl Default for RegexFlags {
+    fn default() -> Self {
+        RegexFlags {
+            case_insensitive: false,
+            multi_line: false,
+            dot_matches_new_line: false,
+            ignore_whitespace: false,
+            swap_greed: false,
+            unicode: true,
+            size_limit: None,
+            dfa_size_limit: None,
+            engine: None,
+            backtrack_limit: None,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub char_start: usize,
+    pub char_end: usize,
+}
+
+#[derive(Serialize, Debug)]
+pub struct RegexGroup {
+    pub index: usize,
+    pub name: Option<String>,
+    /// 分组未参与匹配时为 null
+    pub span: Option<Span>,
+    pub text: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct RegexMatch {
+    pub span: Span,
+    pub text: String,
+    pub groups: Vec<RegexGroup>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct IsMatchResult {
+    /// 实际执行匹配的引擎，`std` 或 `fancy`
+    pub engine: &'static str,
+    pub is_match: bool,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CapturesResult {
+    pub engine: &'static str,
+    pub captures: Vec<String>,
+}
+
+#[tauri::command]
+pub async fn regex_is_match(
+    cache: State<'_, RegexCache>,
+    pattern: String,
+    text: String,
+    flags: Option<RegexFlags>,
+) -> Result<IsMatchResult, String> {
+    let re = cache.get(&pattern, flags.unwrap_or_default())?;
+    run_matcher(text.len(), move || {
+        Ok(IsMatchResult {
+            engine: re.name(),
+            is_match: re.is_match(&text)?,
+        })
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn regex_captures(
+    cache: State<'_, RegexCache>,
+    pattern: String,
+    text: String,
+    flags: Option<RegexFlags>,
+) -> Result<CapturesResult, String> {
+    let re = cache.get(&pattern, flags.unwrap_or_default())?;
+    run_matcher(text.len(), move || {
+        let mut captures = vec![];
+        if let Some(caps) = re.captures_iter(&text, 1)?.first() {
+            for index in 0..caps.len() {
+                if let Some((start, end)) = caps.get(index) {
+                    captures.push(text[start..end].to_owned());
+                }
+            }
+        }
+
+        Ok(CapturesResult {
+            engine: re.name(),
+            captures,
+        })
+    })
+    .await
+}
+
+/// 返回所有匹配及每个分组的位置（字节和字符偏移），`limit` 限制返回的匹配数
+#[tauri::command]
+pub async fn regex_find_all(
+    cache: State<'_, RegexCache>,
+    pattern: String,
+    text: String,
+    limit: Option<usize>,
+    flags: Option<RegexFlags>,
+) -> Result<Vec<RegexMatch>, String> {
+    let re = cache.get(&pattern, flags.unwrap_or_default())?;
+    run_matcher(text.len(), move || {
+        let names = re.capture_names();
+
+        let mut result = vec![];
+        let mut chars = CharOffsets::default();
+        for caps in re.captures_iter(&text, limit.unwrap_or(usize::MAX))? {
+            result.push(to_match(&caps, &names, &text, &mut chars));
+        }
+
+        Ok(result)
+    })
+    .await
+}
+
+#[derive(Serialize, Debug)]
+pub struct Replacement {
+    /// 原文中被替换的位置
+    pub span: Span,
+    /// 结果中替换文本的位置
+    pub output_span: Span,
+    pub matched: String,
+    pub replacement: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ReplaceResult {
+    pub text: String,
+    pub replacements: Vec<Replacement>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SplitPiece {
+    pub span: Span,
+    pub text: String,
+}
+
+/// 正则替换，`replacement` 支持 `$1`、`${name}` 模板
+///
+/// `all` 为 false 时只替换第一个匹配（默认全部替换），`limit` 限制最多替换的次数。
+#[tauri::command]
+pub async fn regex_replace(
+    cache: State<'_, RegexCache>,
+    pattern: String,
+    text: String,
+    replacement: String,
+    all: Option<bool>,
+    limit: Option<usize>,
+    flags: Option<RegexFlags>,
+) -> Result<ReplaceResult, String> {
+    let re = cache.get(&pattern, flags.unwrap_or_default())?;
+    run_matcher(text.len(), move || {
+        replace(&re, &text, &replacement, all, limit)
+    })
+    .await
+}
+
+fn replace(
+    re: &Engine,
+    text: &str,
+    replacement: &str,
+    all: Option<bool>,
+    limit: Option<usize>,
+) -> Result<ReplaceResult, String> {
+    let limit = match (all.unwrap_or(true), limit) {
+        (false, _) => 1,
+        (true, Some(limit)) if limit > 0 => limit,
+        _ => usize::MAX,
+    };
+
+    let mut output = String::with_capacity(text.len());
+    let mut replacements = vec![];
+    let mut chars = CharOffsets::default();
+    let mut output_chars = CharOffsets::default();
+    let mut last = 0;
+    for caps in re.captures_iter(text, limit)? {
+        let (start, end) = caps.get(0).expect("group 0 always participates");
+        output.push_str(&text[last..start]);
+
+        let expanded = caps.expand(replacement);
+        let output_start = output.len();
+        output.push_str(&expanded);
+
+        replacements.push(Replacement {
+            span: chars.span(text, start, end),
+            output_span: output_chars.span(&output, output_start, output.len()),
+            matched: text[start..end].to_owned(),
+            replacement: expanded,
+        });
+        last = end;
+    }
+    output.push_str(&text[last..]);
+
+    Ok(ReplaceResult {
+        text: output,
+        replacements,
+    })
+}
+
+/// 按正则切分文本，`limit` 为最多返回的片段数，最后一段包含剩余的全部文本
 #[tauri::command]
-pub fn regex_is_match(pattern: &str, text: &str) -> Result<bool, String> {
-    match Regex::new(pattern) {
-        Ok(rx) => Ok(rx.is_match(text)),
-        Err(e) => Err(format!("{e:?}")),
+pub async fn regex_split(
+    cache: State<'_, RegexCache>,
+    pattern: String,
+    text: String,
+    limit: Option<usize>,
+    flags: Option<RegexFlags>,
+) -> Result<Vec<SplitPiece>, String> {
+    let re = cache.get(&pattern, flags.unwrap_or_default())?;
+    run_matcher(text.len(), move || split(&re, &text, limit)).await
+}
+
+fn split(re: &Engine, text: &str, limit: Option<usize>) -> Result<Vec<SplitPiece>, String> {
+    let limit = limit.filter(|l| *l > 0).unwrap_or(usize::MAX);
+
+    let mut pieces = vec![];
+    let mut chars = CharOffsets::default();
+    let mut last = 0;
+    for caps in re.captures_iter(text, limit - 1)? {
+        let (start, end) = caps.get(0).expect("group 0 always participates");
+        pieces.push(SplitPiece {
+            span: chars.span(text, last, start),
+            text: text[last..start].to_owned(),
+        });
+        last = end;
     }
+    pieces.push(SplitPiece {
+        span: chars.span(text, last, text.len()),
+        text: text[last..].to_owned(),
+    });
+
+    Ok(pieces)
+}
+
+#[derive(Serialize, Debug)]
+pub struct CacheStats {
+    pub capacity: usize,
+    pub len: usize,
+    pub hits: u64,
+    pub misses: u64,
 }
 
 #[tauri::command]
-pub fn regex_captures(pattern: &str, text: &str) -> Result<Vec<String>, String> {
-    let re = Regex::new(pattern).map_err(|e| format!("{e:?}"))?;
-
-    let mut result = vec![];
-    if let Some(caps) = re.captures(text) {
-        for cap in caps.iter() {
-            if let Some(cap) = cap {
-                result.push(cap.as_str().to_owned());
+pub fn regex_cache_stats(cache: State<'_, RegexCache>) -> CacheStats {
+    cache.stats()
+}
+
+#[tauri::command]
+pub fn regex_cache_clear(cache: State<'_, RegexCache>) {
+    cache.clear();
+}
+
+/// 超过这个长度的文本放到阻塞线程池里匹配，避免占住异步运行时
+const BLOCKING_THRESHOLD: usize = 64 * 1024;
+
+async fn run_matcher<T, F>(text_len: usize, f: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, String> + Send + 'static,
+{
+    if text_len < BLOCKING_THRESHOLD {
+        return f();
+    }
+    tauri::async_runtime::spawn_blocking(f)
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// 按模式和标志缓存编译结果的 LRU，作为 Tauri managed state 在各次调用间共享
+pub struct RegexCache {
+    inner: Mutex<CacheInner>,
+}
+
+struct CacheInner {
+    entries: LruCache<(String, RegexFlags), Arc<Engine>>,
+    hits: u64,
+    misses: u64,
+}
+
+impl RegexCache {
+    pub fn new(capacity: usize) -> RegexCache {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        RegexCache {
+            inner: Mutex::new(CacheInner {
+                entries: LruCache::new(capacity),
+                hits: 0,
+                misses: 0,
+            }),
+        }
+    }
+
+    /// 未命中时在锁外编译，编译失败不缓存
+    pub fn get(&self, pattern: &str, flags: RegexFlags) -> Result<Arc<Engine>, String> {
+        let key = (pattern.to_owned(), flags);
+        {
+            let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(re) = inner.entries.get(&key).cloned() {
+                inner.hits += 1;
+                return Ok(re);
             }
+            inner.misses += 1;
         }
+
+        let re = Arc::new(build_engine(&key.0, &key.1)?);
+        self.inner
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entries
+            .put(key, re.clone());
+        Ok(re)
     }
 
-    Ok(result)
+    pub fn stats(&self) -> CacheStats {
+        let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        CacheStats {
+            capacity: inner.entries.cap().get(),
+            len: inner.entries.len(),
+            hits: inner.hits,
+            misses: inner.misses,
+        }
+    }
+
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner.entries.clear();
+        inner.hits = 0;
+        inner.misses = 0;
+    }
+}
+
+/// 编译后的正则：`Std` 为 regex crate，`Fancy` 为支持环视和反向引用的回溯引擎
+pub enum Engine {
+    Std(Regex),
+    Fancy(fancy_regex::Regex, usize),
+}
+
+/// 一次匹配中各分组的字节范围，两种引擎共用
+pub enum EngineCaptures<'t> {
+    Std(regex::Captures<'t>),
+    Fancy(fancy_regex::Captures<'t>),
+}
+
+impl Engine {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Engine::Std(_) => "std",
+            Engine::Fancy(..) => "fancy",
+        }
+    }
+
+    pub fn is_match(&self, text: &str) -> Result<bool, String> {
+        match self {
+            Engine::Std(re) => Ok(re.is_match(text)),
+            Engine::Fancy(re, limit) => re.is_match(text).map_err(|e| fancy_error(&e, *limit, 0)),
+        }
+    }
+
+    pub fn capture_names(&self) -> Vec<Option<String>> {
+        match self {
+            Engine::Std(re) => re
+                .capture_names()
+                .map(|n| n.map(|n| n.to_owned()))
+                .collect(),
+            Engine::Fancy(re, _) => re
+                .capture_names()
+                .map(|n| n.map(|n| n.to_owned()))
+                .collect(),
+        }
+    }
+
+    /// 最多 `limit` 个匹配，fancy 引擎超出回溯上限时返回错误
+    pub fn captures_iter<'t>(
+        &self,
+        text: &'t str,
+        limit: usize,
+    ) -> Result<Vec<EngineCaptures<'t>>, String> {
+        match self {
+            Engine::Std(re) => Ok(re
+                .captures_iter(text)
+                .take(limit)
+                .map(EngineCaptures::Std)
+                .collect()),
+            Engine::Fancy(re, backtrack_limit) => re
+                .captures_iter(text)
+                .take(limit)
+                .map(|caps| {
+                    caps.map(EngineCaptures::Fancy)
+                        .map_err(|e| fancy_error(&e, *backtrack_limit, 0))
+                })
+                .collect(),
+        }
+    }
+}
+
+#[allow(clippy::len_without_is_empty)]
+impl EngineCaptures<'_> {
+    pub fn len(&self) -> usize {
+        match self {
+            EngineCaptures::Std(caps) => caps.len(),
+            EngineCaptures::Fancy(caps) => caps.len(),
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Option<(usize, usize)> {
+        match self {
+            EngineCaptures::Std(caps) => caps.get(index).map(|m| (m.start(), m.end())),
+            EngineCaptures::Fancy(caps) => caps.get(index).map(|m| (m.start(), m.end())),
+        }
+    }
+
+    pub fn expand(&self, replacement: &str) -> String {
+        let mut expanded = String::new();
+        match self {
+            EngineCaptures::Std(caps) => caps.expand(replacement, &mut expanded),
+            EngineCaptures::Fancy(caps) => caps.expand(replacement, &mut expanded),
+        }
+        expanded
+    }
+}
+
+/// 按 `flags.engine` 选择引擎，`auto` 先用标准引擎，遇到不支持的语法再改用 fancy
+pub fn build_engine(pattern: &str, flags: &RegexFlags) -> Result<Engine, String> {
+    match flags.engine.as_deref().unwrap_or("std") {
+        "std" => build_regex(pattern, flags).map(Engine::Std),
+        "fancy" | "pcre" => build_fancy(pattern, flags),
+        "auto" => match build_regex(pattern, flags) {
+            Ok(re) => Ok(Engine::Std(re)),
+            Err(e) => build_fancy(pattern, flags).map_err(|_| e),
+        },
+        other => Err(format!("unknown regex engine: {other}")),
+    }
+}
+
+/// fancy-regex 的 builder 没有标志选项，改用模式前缀的内联标志
+fn build_fancy(pattern: &str, flags: &RegexFlags) -> Result<Engine, String> {
+    if !flags.unicode {
+        return Err("the fancy engine does not support disabling Unicode".to_string());
+    }
+
+    let mut inline = String::new();
+    for (enabled, flag) in [
+        (flags.case_insensitive, 'i'),
+        (flags.multi_line, 'm'),
+        (flags.dot_matches_new_line, 's'),
+        (flags.ignore_whitespace, 'x'),
+        (flags.swap_greed, 'U'),
+    ] {
+        if enabled {
+            inline.push(flag);
+        }
+    }
+    let prefix = if inline.is_empty() {
+        String::new()
+    } else {
+        format!("(?{inline})")
+    };
+
+    let backtrack_limit = flags.backtrack_limit.unwrap_or(1_000_000);
+    let mut builder = fancy_regex::RegexBuilder::new(&format!("{prefix}{pattern}"));
+    builder.backtrack_limit(backtrack_limit);
+    if let Some(limit) = flags.size_limit {
+        builder.delegate_size_limit(limit);
+    }
+    if let Some(limit) = flags.dfa_size_limit {
+        builder.delegate_dfa_size_limit(limit);
+    }
+
+    let re = builder
+        .build()
+        .map_err(|e| fancy_error(&e, backtrack_limit, prefix.len()))?;
+    Ok(Engine::Fancy(re, backtrack_limit))
+}
+
+pub fn build_regex(pattern: &str, flags: &RegexFlags) -> Result<Regex, String> {
+    let mut builder = RegexBuilder::new(pattern);
+    builder
+        .case_insensitive(flags.case_insensitive)
+        .multi_line(flags.multi_line)
+        .dot_matches_new_line(flags.dot_matches_new_line)
+        .ignore_whitespace(flags.ignore_whitespace)
+        .swap_greed(flags.swap_greed)
+        .unicode(flags.unicode);
+    if let Some(limit) = flags.size_limit {
+        builder.size_limit(limit);
+    }
+    if let Some(limit) = flags.dfa_size_limit {
+        builder.dfa_size_limit(limit);
+    }
+
+    builder.build().map_err(|e| regex_error(&e))
+}
+
+fn regex_error(e: &regex::Error) -> String {
+    match e {
+        regex::Error::Syntax(msg) => msg.to_owned(),
+        regex::Error::CompiledTooBig(limit) => format!(
+            "compiled regex exceeds the size limit of {limit} bytes, simplify the pattern or raise size_limit"
+        ),
+        e => e.to_string(),
+    }
+}
+
+/// 解析错误的位置去掉内联标志前缀，指向用户输入的模式
+fn fancy_error(e: &fancy_regex::Error, backtrack_limit: usize, prefix: usize) -> String {
+    match e {
+        fancy_regex::Error::ParseError(position, e) => {
+            format!(
+                "parse error at position {}: {e}",
+                position.saturating_sub(prefix)
+            )
+        }
+        fancy_regex::Error::RuntimeError(fancy_regex::RuntimeError::BacktrackLimitExceeded) => {
+            format!(
+                "backtracking exceeded the limit of {backtrack_limit} steps, simplify the pattern or raise backtrack_limit"
+            )
+        }
+        e => e.to_string(),
+    }
+}
+
+fn to_match(
+    caps: &EngineCaptures,
+    names: &[Option<String>],
+    text: &str,
+    chars: &mut CharOffsets,
+) -> RegexMatch {
+    let (start, end) = caps.get(0).expect("group 0 always participates");
+    let span = chars.span(text, start, end);
+
+    let groups = names
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(index, name)| {
+            let group = caps.get(index);
+            RegexGroup {
+                index,
+                name: name.to_owned(),
+                span: group.map(|(start, end)| chars.span(text, start, end)),
+                text: group.map(|(start, end)| text[start..end].to_owned()),
+            }
+        })
+        .collect();
+
+    RegexMatch {
+        span,
+        text: text[start..end].to_owned(),
+        groups,
+    }
+}
+
+/// 字节偏移转字符偏移，匹配按顺序出现，从上次的位置继续数
+#[derive(Default)]
+struct CharOffsets {
+    byte: usize,
+    char: usize,
+}
+
+impl CharOffsets {
+    fn offset(&mut self, text: &str, byte: usize) -> usize {
+        if byte < self.byte {
+            return text[..byte].chars().count();
+        }
+        self.char += text[self.byte..byte].chars().count();
+        self.byte = byte;
+        self.char
+    }
+
+    fn span(&mut self, text: &str, start: usize, end: usize) -> Span {
+        let char_start = self.offset(text, start);
+        let char_end = char_start + text[start..end].chars().count();
+        Span {
+            start,
+            end,
+            char_start,
+            char_end,
+        }
+    }
 }
@@ -0,0 +1,457 @@
+use regex_syntax::ast::{
+    self, parse::ParserBuilder, AssertionKind, Ast, ClassPerlKind, ClassSet, ClassSetItem,
+    GroupKind, LiteralKind, RepetitionKind, RepetitionRange,
+};
+use serde::Serialize;
+
+use crate::regex::RegexFlags;
+
+#[derive(Serialize, Debug)]
+pub struct ExplainSpan {
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ExplainNode {
+    pub kind: &'static str,
+    pub span: ExplainSpan,
+    pub text: String,
+    pub description: String,
+    pub children: Vec<ExplainNode>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ExplainWarning {
+    pub span: ExplainSpan,
+    pub message: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct RegexExplain {
+    pub tree: ExplainNode,
+    pub warnings: Vec<ExplainWarning>,
+}
+
+/// 用 regex-syntax 解析正则，返回语法树、每个节点的文字说明以及常见错误提示
+#[tauri::command]
+pub fn regex_explain(pattern: &str, flags: Option<RegexFlags>) -> Result<RegexExplain, String> {
+    let flags = flags.unwrap_or_default();
+    let ast = ParserBuilder::new()
+        .ignore_whitespace(flags.ignore_whitespace)
+        .build()
+        .parse(pattern)
+        .map_err(|e| e.to_string())?;
+
+    let explainer = Explainer { pattern };
+    let mut warnings = vec![];
+    explainer.lint(&ast, 0, &mut warnings);
+
+    Ok(RegexExplain {
+        tree: explainer.node(&ast),
+        warnings,
+    })
+}
+
+/// 单数和复数两种说法，量词需要复数形式
+struct Phrase {
+    one: String,
+    many: String,
+}
+
+impl Phrase {
+    fn new(one: impl Into<String>, many: impl Into<String>) -> Phrase {
+        Phrase {
+            one: one.into(),
+            many: many.into(),
+        }
+    }
+
+    /// 复杂的子表达式没有自然的复数形式
+    fn compound(one: String) -> Phrase {
+        let many = format!("repetitions of ({one})");
+        Phrase { one, many }
+    }
+}
+
+struct Explainer<'p> {
+    pattern: &'p str,
+}
+
+impl<'p> Explainer<'p> {
+    fn text(&self, span: &ast::Span) -> &'p str {
+        &self.pattern[span.start.offset..span.end.offset]
+    }
+
+    fn node(&self, ast: &Ast) -> ExplainNode {
+        let span = ast.span();
+        let children = match ast {
+            Ast::Repetition(rep) => vec![self.node(&rep.ast)],
+            Ast::Group(group) => vec![self.node(&group.ast)],
+            Ast::Alternation(alt) => alt.asts.iter().map(|a| self.node(a)).collect(),
+            Ast::Concat(concat) => self.concat_children(&concat.asts),
+            _ => vec![],
+        };
+
+        ExplainNode {
+            kind: kind_name(ast),
+            span: ExplainSpan {
+                start: span.start.offset,
+                end: span.end.offset,
+            },
+            text: self.text(span).to_owned(),
+            description: self.phrase(ast).one,
+            children,
+        }
+    }
+
+    /// 连续的字面字符合并成一个节点
+    fn concat_children(&self, asts: &[Ast]) -> Vec<ExplainNode> {
+        let mut children = vec![];
+        let mut i = 0;
+        while i < asts.len() {
+            let run = literal_run(&asts[i..]);
+            if run.len() > 1 {
+                let start = run[0].span.start.offset;
+                let end = run[run.len() - 1].span.end.offset;
+                let text: String = run.iter().map(|l| l.c).collect();
+                children.push(ExplainNode {
+                    kind: "literal",
+                    span: ExplainSpan { start, end },
+                    text: self.pattern[start..end].to_owned(),
+                    description: format!("the text {text:?}"),
+                    children: vec![],
+                });
+                i += run.len();
+            } else {
+                children.push(self.node(&asts[i]));
+                i += 1;
+            }
+        }
+        children
+    }
+
+    fn phrase(&self, ast: &Ast) -> Phrase {
+        match ast {
+            Ast::Empty(_) => Phrase::new("nothing (matches the empty string)", "empty strings"),
+            Ast::Flags(set) => Phrase::compound(format!(
+                "set flags {} for the rest of the group",
+                self.text(&set.flags.span)
+            )),
+            Ast::Literal(lit) => literal_phrase(lit),
+            Ast::Dot(_) => Phrase::new(
+                "any character except a newline",
+                "characters (any except newline)",
+            ),
+            Ast::Assertion(assertion) => Phrase::compound(
+                match assertion.kind {
+                    AssertionKind::StartLine => {
+                        "the start of the text (or of a line with the m flag)"
+                    }
+                    AssertionKind::EndLine => "the end of the text (or of a line with the m flag)",
+                    AssertionKind::StartText => "the start of the text",
+                    AssertionKind::EndText => "the end of the text",
+                    AssertionKind::WordBoundary => "a word boundary",
+                    AssertionKind::NotWordBoundary => "a position that is not a word boundary",
+                    AssertionKind::WordBoundaryStart | AssertionKind::WordBoundaryStartAngle => {
+                        "the start of a word"
+                    }
+                    AssertionKind::WordBoundaryEnd | AssertionKind::WordBoundaryEndAngle => {
+                        "the end of a word"
+                    }
+                    AssertionKind::WordBoundaryStartHalf => {
+                        "a position not preceded by a word character"
+                    }
+                    AssertionKind::WordBoundaryEndHalf => {
+                        "a position not followed by a word character"
+                    }
+                }
+                .to_string(),
+            ),
+            Ast::ClassPerl(class) => {
+                let (one, many) = match (&class.kind, class.negated) {
+                    (ClassPerlKind::Digit, false) => ("a digit", "digits"),
+                    (ClassPerlKind::Digit, true) => {
+                        ("a non-digit character", "non-digit characters")
+                    }
+                    (ClassPerlKind::Word, false) => ("a word character", "word characters"),
+                    (ClassPerlKind::Word, true) => ("a non-word character", "non-word characters"),
+                    (ClassPerlKind::Space, false) => {
+                        ("a whitespace character", "whitespace characters")
+                    }
+                    (ClassPerlKind::Space, true) => {
+                        ("a non-whitespace character", "non-whitespace characters")
+                    }
+                };
+                Phrase::new(one, many)
+            }
+            Ast::ClassUnicode(class) => {
+                let text = self.text(&class.span);
+                if class.negated {
+                    Phrase::new(
+                        format!("a character outside Unicode class {text}"),
+                        format!("characters outside Unicode class {text}"),
+                    )
+                } else {
+                    Phrase::new(
+                        format!("a character in Unicode class {text}"),
+                        format!("characters in Unicode class {text}"),
+                    )
+                }
+            }
+            Ast::ClassBracketed(class) => {
+                let text = self.text(&class.span);
+                if class.negated {
+                    Phrase::new(
+                        format!("any character not listed in {text}"),
+                        format!("characters not listed in {text}"),
+                    )
+                } else {
+                    Phrase::new(
+                        format!("one character from {text}"),
+                        format!("characters from {text}"),
+                    )
+                }
+            }
+            Ast::Repetition(rep) => {
+                let inner = self.phrase(&rep.ast);
+                let mut one = match &rep.op.kind {
+                    RepetitionKind::ZeroOrOne => format!("optionally {}", inner.one),
+                    RepetitionKind::ZeroOrMore => format!("zero or more {}", inner.many),
+                    RepetitionKind::OneOrMore => format!("one or more {}", inner.many),
+                    RepetitionKind::Range(RepetitionRange::Exactly(n)) => {
+                        format!("exactly {n} {}", inner.many)
+                    }
+                    RepetitionKind::Range(RepetitionRange::AtLeast(n)) => {
+                        format!("at least {n} {}", inner.many)
+                    }
+                    RepetitionKind::Range(RepetitionRange::Bounded(m, n)) => {
+                        format!("between {m} and {n} {}", inner.many)
+                    }
+                };
+                if !rep.greedy {
+                    one.push_str(" (lazy: as few as possible)");
+                }
+                Phrase::compound(one)
+            }
+            Ast::Group(group) => {
+                let inner = self.phrase(&group.ast);
+                match &group.kind {
+                    GroupKind::CaptureIndex(index) => {
+                        Phrase::compound(format!("{}, captured as group {index}", inner.one))
+                    }
+                    GroupKind::CaptureName { name, .. } => Phrase::compound(format!(
+                        "{}, captured as group `{}` ({})",
+                        inner.one, name.name, name.index
+                    )),
+                    GroupKind::NonCapturing(flags) if !flags.items.is_empty() => Phrase::compound(
+                        format!("{} (with flags {})", inner.one, self.text(&flags.span)),
+                    ),
+                    GroupKind::NonCapturing(_) => inner,
+                }
+            }
+            Ast::Alternation(alt) => {
+                let options = alt
+                    .asts
+                    .iter()
+                    .map(|a| self.phrase(a).one)
+                    .collect::<Vec<_>>();
+                Phrase::compound(format!("either {}", options.join(", or ")))
+            }
+            Ast::Concat(concat) => {
+                let mut parts = vec![];
+                let mut i = 0;
+                while i < concat.asts.len() {
+                    let run = literal_run(&concat.asts[i..]);
+                    if run.len() > 1 {
+                        let text: String = run.iter().map(|l| l.c).collect();
+                        parts.push(format!("the text {text:?}"));
+                        i += run.len();
+                    } else {
+                        parts.push(self.phrase(&concat.asts[i]).one);
+                        i += 1;
+                    }
+                }
+                Phrase::compound(parts.join(", then "))
+            }
+        }
+    }
+
+    fn lint(&self, ast: &Ast, groups: usize, warnings: &mut Vec<ExplainWarning>) {
+        let warn = |warnings: &mut Vec<ExplainWarning>, span: &ast::Span, message: &str| {
+            warnings.push(ExplainWarning {
+                span: ExplainSpan {
+                    start: span.start.offset,
+                    end: span.end.offset,
+                },
+                message: message.to_string(),
+            });
+        };
+
+        match ast {
+            Ast::Concat(concat) => {
+                for (i, item) in concat.asts.iter().enumerate() {
+                    // example.com 中未转义的点
+                    if let Ast::Dot(span) = item {
+                        let before = i > 0 && is_word_like(&concat.asts[i - 1]);
+                        let after = concat.asts.get(i + 1).is_some_and(is_word_like);
+                        if before && after {
+                            warn(
+                                warnings,
+                                span,
+                                "unescaped `.` matches any character; use `\\.` to match a literal dot (e.g. in hostnames)",
+                            );
+                        }
+                    }
+                    self.lint(item, groups, warnings);
+                }
+            }
+            Ast::Repetition(rep) => {
+                if rep.greedy
+                    && groups > 0
+                    && matches!(*rep.ast, Ast::Dot(_))
+                    && matches!(
+                        rep.op.kind,
+                        RepetitionKind::ZeroOrMore | RepetitionKind::OneOrMore
+                    )
+                {
+                    warn(
+                        warnings,
+                        &rep.span,
+                        "greedy `.*` inside a group captures as much as possible, often past the intended delimiter; consider `.*?` or a negated class such as `[^,]*`",
+                    );
+                }
+                if has_unbounded_repetition(&rep.ast) && is_unbounded(&rep.op.kind) {
+                    warn(
+                        warnings,
+                        &rep.span,
+                        "nested unbounded quantifiers like `(a+)+` can cause catastrophic backtracking in PCRE-style engines",
+                    );
+                }
+                self.lint(&rep.ast, groups, warnings);
+            }
+            Ast::Group(group) => self.lint(&group.ast, groups + 1, warnings),
+            Ast::Alternation(alt) => {
+                if alt.asts.iter().any(|a| matches!(a, Ast::Empty(_))) {
+                    warn(
+                        warnings,
+                        &alt.span,
+                        "empty alternative matches the empty string; use `?` if the group is meant to be optional",
+                    );
+                }
+                for item in &alt.asts {
+                    self.lint(item, groups, warnings);
+                }
+            }
+            Ast::ClassBracketed(class) => {
+                for range in class_ranges(&class.kind) {
+                    if range.start.c <= 'Z' && range.end.c >= 'a' && range.start.c >= 'A' {
+                        warn(
+                            warnings,
+                            &range.span,
+                            "range `A-z` also includes [ \\ ] ^ _ and `; use `A-Za-z` instead",
+                        );
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn kind_name(ast: &Ast) -> &'static str {
+    match ast {
+        Ast::Empty(_) => "empty",
+        Ast::Flags(_) => "flags",
+        Ast::Literal(_) => "literal",
+        Ast::Dot(_) => "dot",
+        Ast::Assertion(_) => "assertion",
+        Ast::ClassUnicode(_) | Ast::ClassPerl(_) | Ast::ClassBracketed(_) => "class",
+        Ast::Repetition(_) => "repetition",
+        Ast::Group(_) => "group",
+        Ast::Alternation(_) => "alternation",
+        Ast::Concat(_) => "concat",
+    }
+}
+
+fn literal_run(asts: &[Ast]) -> Vec<&ast::Literal> {
+    asts.iter()
+        .map_while(|a| match a {
+            Ast::Literal(lit) => Some(lit.as_ref()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn literal_phrase(lit: &ast::Literal) -> Phrase {
+    let name = match lit.c {
+        '\n' => "a newline".to_string(),
+        '\r' => "a carriage return".to_string(),
+        '\t' => "a tab".to_string(),
+        ' ' => "a space".to_string(),
+        c if matches!(lit.kind, LiteralKind::Meta | LiteralKind::Superfluous) => {
+            format!("a literal '{c}'")
+        }
+        c => format!("the character '{c}'"),
+    };
+    let many = format!(
+        "{} characters",
+        name.trim_start_matches("a ")
+            .trim_start_matches("the character ")
+    );
+    Phrase::new(name, many)
+}
+
+fn is_word_like(ast: &Ast) -> bool {
+    match ast {
+        Ast::Literal(lit) => lit.c.is_alphanumeric(),
+        Ast::ClassPerl(class) => !class.negated,
+        Ast::ClassBracketed(_) => true,
+        Ast::Repetition(rep) => is_word_like(&rep.ast),
+        _ => false,
+    }
+}
+
+fn is_unbounded(kind: &RepetitionKind) -> bool {
+    matches!(
+        kind,
+        RepetitionKind::ZeroOrMore
+            | RepetitionKind::OneOrMore
+            | RepetitionKind::Range(RepetitionRange::AtLeast(_))
+    )
+}
+
+fn has_unbounded_repetition(ast: &Ast) -> bool {
+    match ast {
+        Ast::Repetition(rep) => is_unbounded(&rep.op.kind) || has_unbounded_repetition(&rep.ast),
+        Ast::Group(group) => has_unbounded_repetition(&group.ast),
+        Ast::Concat(concat) => concat.asts.iter().any(has_unbounded_repetition),
+        Ast::Alternation(alt) => alt.asts.iter().any(has_unbounded_repetition),
+        _ => false,
+    }
+}
+
+fn class_ranges(set: &ClassSet) -> Vec<&ast::ClassSetRange> {
+    fn collect<'a>(item: &'a ClassSetItem, ranges: &mut Vec<&'a ast::ClassSetRange>) {
+        match item {
+            ClassSetItem::Range(range) => ranges.push(range),
+            ClassSetItem::Union(union) => {
+                for item in &union.items {
+                    collect(item, ranges);
+                }
+            }
+            ClassSetItem::Bracketed(class) => ranges.extend(class_ranges(&class.kind)),
+            _ => {}
+        }
+    }
+
+    let mut ranges = vec![];
+    match set {
+        ClassSet::Item(item) => collect(item, &mut ranges),
+        ClassSet::BinaryOp(op) => {
+            ranges.extend(class_ranges(&op.lhs));
+            ranges.extend(class_ranges(&op.rhs));
+        }
+    }
+    ranges
+}
@@ -0,0 +1,351 @@
+use serde::{Deserialize, Serialize};
+
+use crate::regex::{build_regex, RegexFlags};
+
+/// 与 grex 类似的生成选项
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct GenerateOptions {
+    /// 数字泛化为 `\d`
+    pub digits: bool,
+    /// 空白泛化为 `\s`
+    pub spaces: bool,
+    /// 字母、数字和下划线泛化为 `\w`
+    pub words: bool,
+    /// 检测连续重复的字符或子串，如 `a{3}`、`(?:ab){2}`
+    pub repetitions: bool,
+    /// 用 `^...$` 包住整个表达式
+    pub anchors: bool,
+    pub case_insensitive: bool,
+}
+
+impl Default for GenerateOptions {
+    fn default() -> Self {
+        GenerateOptions {
+            digits: false,
+            spaces: false,
+            words: false,
+            repetitions: false,
+            anchors: true,
+            case_insensitive: false,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct GenerateResult {
+    pub pattern: String,
+    /// 为排除反例而关闭的泛化选项
+    pub relaxed: Vec<&'static str>,
+    /// 校验失败的样例，为空表示所有正例匹配、所有反例不匹配
+    pub failures: Vec<String>,
+}
+
+/// 根据正例生成正则，再用 regex 引擎校验正例和反例
+///
+/// 生成的表达式匹配了反例时，依次关闭 `words`、`spaces`、`digits` 泛化重试。
+#[tauri::command]
+pub fn regex_generate(
+    positive: Vec<String>,
+    negative: Option<Vec<String>>,
+    options: Option<GenerateOptions>,
+) -> Result<GenerateResult, String> {
+    if positive.is_empty() {
+        return Err("at least one positive example is required".to_string());
+    }
+    let negative = negative.unwrap_or_default();
+    let mut options = options.unwrap_or_default();
+
+    let mut relaxed = vec![];
+    loop {
+        let pattern = generate(&positive, &options);
+        let failures = verify(&pattern, &positive, &negative)?;
+
+        let next = if options.words {
+            options.words = false;
+            "words"
+        } else if options.spaces {
+            options.spaces = false;
+            "spaces"
+        } else if options.digits {
+            options.digits = false;
+            "digits"
+        } else {
+            ""
+        };
+        if failures.is_empty() || next.is_empty() {
+            return Ok(GenerateResult {
+                pattern,
+                relaxed,
+                failures,
+            });
+        }
+        relaxed.push(next);
+    }
+}
+
+fn verify(pattern: &str, positive: &[String], negative: &[String]) -> Result<Vec<String>, String> {
+    let re = build_regex(pattern, &RegexFlags::default())?;
+
+    let mut failures = vec![];
+    for text in positive {
+        if !re.is_match(text) {
+            failures.push(format!("positive example {text:?} does not match"));
+        }
+    }
+    for text in negative {
+        if re.is_match(text) {
+            failures.push(format!("negative example {text:?} matches"));
+        }
+    }
+    Ok(failures)
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum Token {
+    Char(char),
+    Digit,
+    Space,
+    Word,
+}
+
+/// 重复 `count` 次的单元，未检测重复时单元只有一个字符
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct Atom {
+    unit: Vec<Token>,
+    count: usize,
+}
+
+#[derive(Default, Debug)]
+struct Node {
+    children: Vec<(Atom, Node)>,
+    terminal: bool,
+}
+
+fn generate(examples: &[String], options: &GenerateOptions) -> String {
+    let mut root = Node::default();
+    for example in examples {
+        let example = if options.case_insensitive {
+            example.to_lowercase()
+        } else {
+            example.to_owned()
+        };
+        let tokens = example
+            .chars()
+            .map(|c| tokenize(c, options))
+            .collect::<Vec<_>>();
+        let atoms = if options.repetitions {
+            fold_repetitions(&tokens)
+        } else {
+            tokens
+                .into_iter()
+                .map(|t| Atom {
+                    unit: vec![t],
+                    count: 1,
+                })
+                .collect()
+        };
+
+        let mut node = &mut root;
+        for atom in atoms {
+            let index = match node.children.iter().position(|(a, _)| *a == atom) {
+                Some(index) => index,
+                None => {
+                    node.children.push((atom, Node::default()));
+                    node.children.len() - 1
+                }
+            };
+            node = &mut node.children[index].1;
+        }
+        node.terminal = true;
+    }
+
+    let mut pattern = String::new();
+    if options.case_insensitive {
+        pattern.push_str("(?i)");
+    }
+    if options.anchors {
+        pattern.push('^');
+    }
+    pattern.push_str(&render(&root));
+    if options.anchors {
+        pattern.push('$');
+    }
+    pattern
+}
+
+fn tokenize(c: char, options: &GenerateOptions) -> Token {
+    if options.digits && c.is_ascii_digit() {
+        Token::Digit
+    } else if options.spaces && c.is_whitespace() {
+        Token::Space
+    } else if options.words && (c.is_alphanumeric() || c == '_') {
+        Token::Word
+    } else {
+        Token::Char(c)
+    }
+}
+
+/// 在每个位置找覆盖最长的连续重复单元，长度相同时取较短的单元
+fn fold_repetitions(tokens: &[Token]) -> Vec<Atom> {
+    let mut atoms = vec![];
+    let mut i = 0;
+    while i < tokens.len() {
+        let mut best = (1, 1);
+        for len in 1..=(tokens.len() - i) / 2 {
+            let unit = &tokens[i..i + len];
+            let mut count = 1;
+            while tokens[i + count * len..].starts_with(unit) {
+                count += 1;
+            }
+            if count > 1 && len * count > best.0 * best.1 {
+                best = (len, count);
+            }
+        }
+
+        let (len, count) = best;
+        atoms.push(Atom {
+            unit: tokens[i..i + len].to_vec(),
+            count,
+        });
+        i += len * count;
+    }
+    atoms
+}
+
+/// 分支项：单元、重复次数范围和后续部分
+struct Branch<'a> {
+    unit: &'a [Token],
+    min: usize,
+    max: usize,
+    rest: String,
+}
+
+fn render(node: &Node) -> String {
+    // 后续部分相同、重复次数连续的分支合并为 {min,max}
+    let rendered = node
+        .children
+        .iter()
+        .map(|(atom, child)| (atom, render(child)))
+        .collect::<Vec<_>>();
+    let mut branches: Vec<Branch> = vec![];
+    let mut used = vec![false; rendered.len()];
+    for i in 0..rendered.len() {
+        if used[i] {
+            continue;
+        }
+        let (atom, rest) = &rendered[i];
+        let mut counts = vec![];
+        for j in i..rendered.len() {
+            if !used[j] && rendered[j].0.unit == atom.unit && rendered[j].1 == *rest {
+                counts.push(rendered[j].0.count);
+                used[j] = true;
+            }
+        }
+        counts.sort_unstable();
+
+        let mut min = counts[0];
+        for (k, &count) in counts.iter().enumerate() {
+            if counts.get(k + 1) != Some(&(count + 1)) {
+                branches.push(Branch {
+                    unit: &atom.unit,
+                    min,
+                    max: count,
+                    rest: rest.to_owned(),
+                });
+                if let Some(&next) = counts.get(k + 1) {
+                    min = next;
+                }
+            }
+        }
+    }
+
+    // 后续部分相同的单个字符合并为字符类
+    let mut alternatives: Vec<String> = vec![];
+    let mut merged = vec![false; branches.len()];
+    for i in 0..branches.len() {
+        if merged[i] {
+            continue;
+        }
+        let single = |b: &Branch| b.unit.len() == 1 && b.min == 1 && b.max == 1;
+        let mut members = vec![&branches[i].unit[0]];
+        if single(&branches[i]) {
+            for j in i + 1..branches.len() {
+                if !merged[j] && single(&branches[j]) && branches[j].rest == branches[i].rest {
+                    members.push(&branches[j].unit[0]);
+                    merged[j] = true;
+                }
+            }
+        }
+
+        let head = if members.len() > 1 {
+            let items = members.iter().map(|t| class_item(t)).collect::<String>();
+            format!("[{items}]")
+        } else {
+            let branch = &branches[i];
+            let unit = if branch.unit.len() == 1 {
+                token(&branch.unit[0])
+            } else {
+                format!("(?:{})", branch.unit.iter().map(token).collect::<String>())
+            };
+            match (branch.min, branch.max) {
+                (1, 1) => unit,
+                (min, max) if min == max => format!("{unit}{{{min}}}"),
+                (min, max) => format!("{unit}{{{min},{max}}}"),
+            }
+        };
+        alternatives.push(format!("{head}{}", branches[i].rest));
+    }
+
+    match alternatives.len() {
+        0 => String::new(),
+        1 if node.terminal => {
+            let only = &alternatives[0];
+            if is_atomic(only) {
+                format!("{only}?")
+            } else {
+                format!("(?:{only})?")
+            }
+        }
+        1 => alternatives.remove(0),
+        _ => {
+            let group = format!("(?:{})", alternatives.join("|"));
+            if node.terminal {
+                format!("{group}?")
+            } else {
+                group
+            }
+        }
+    }
+}
+
+/// 能直接加 `?` 的单个元素：一个字符、转义、字符类或分组
+fn is_atomic(s: &str) -> bool {
+    let mut chars = s.chars();
+    match (chars.next(), s.chars().count()) {
+        (Some(_), 1) => true,
+        (Some('\\'), 2) => true,
+        (Some('['), _) => s.ends_with(']') && s.matches(']').count() == 1,
+        _ => false,
+    }
+}
+
+fn token(token: &Token) -> String {
+    match token {
+        Token::Digit => "\\d".to_string(),
+        Token::Space => "\\s".to_string(),
+        Token::Word => "\\w".to_string(),
+        Token::Char('\n') => "\\n".to_string(),
+        Token::Char('\r') => "\\r".to_string(),
+        Token::Char('\t') => "\\t".to_string(),
+        Token::Char(c) => regex::escape(&c.to_string()),
+    }
+}
+
+fn class_item(item: &Token) -> String {
+    match item {
+        Token::Char(c @ ('\\' | ']' | '[' | '^' | '-' | '&' | '~')) => format!("\\{c}"),
+        Token::Char(c) if !matches!(c, '\n' | '\r' | '\t') => c.to_string(),
+        other => token(other),
+    }
+}
@@ -0,0 +1,130 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::regex::{build_engine, RegexFlags};
+
+/// 保存到仓库里的测试集文件格式
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RegexSuite {
+    pub pattern: String,
+    #[serde(default)]
+    pub flags: RegexFlags,
+    pub cases: Vec<RegexTestCase>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RegexTestCase {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub input: String,
+    #[serde(default = "default_should_match")]
+    pub should_match: bool,
+    /// 分组序号或名称到期望值，null 表示该分组不应参与匹配
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub groups: BTreeMap<String, Option<String>>,
+}
+
+fn default_should_match() -> bool {
+    true
+}
+
+#[derive(Serialize, Debug)]
+pub struct CaseResult {
+    pub name: Option<String>,
+    pub input: String,
+    pub passed: bool,
+    pub matched: bool,
+    /// 未通过的原因
+    pub failures: Vec<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SuiteResult {
+    pub engine: &'static str,
+    pub passed: usize,
+    pub failed: usize,
+    pub cases: Vec<CaseResult>,
+}
+
+/// 运行测试集，每个用例检查是否匹配以及第一个匹配中各分组的值
+#[tauri::command]
+pub fn regex_test_suite(suite: RegexSuite) -> Result<SuiteResult, String> {
+    let re = build_engine(&suite.pattern, &suite.flags)?;
+    let names = re.capture_names();
+
+    let mut cases = vec![];
+    for case in suite.cases {
+        let found = re.captures_iter(&case.input, 1)?;
+        let caps = found.first();
+        let matched = caps.is_some();
+
+        let mut failures = vec![];
+        if matched != case.should_match {
+            failures.push(if case.should_match {
+                "expected a match, found none".to_string()
+            } else {
+                "expected no match, but the pattern matched".to_string()
+            });
+        }
+        if let Some(caps) = caps {
+            for (group, expected) in &case.groups {
+                let index = match group.parse::<usize>() {
+                    Ok(index) => Some(index),
+                    Err(_) => names.iter().position(|n| n.as_deref() == Some(group)),
+                };
+                let Some(index) = index.filter(|i| *i < names.len()) else {
+                    failures.push(format!("group {group} does not exist in the pattern"));
+                    continue;
+                };
+                let actual = caps
+                    .get(index)
+                    .map(|(start, end)| case.input[start..end].to_owned());
+                if &actual != expected {
+                    failures.push(format!(
+                        "group {group}: expected {}, got {}",
+                        describe(expected),
+                        describe(&actual)
+                    ));
+                }
+            }
+        }
+
+        cases.push(CaseResult {
+            name: case.name,
+            input: case.input,
+            passed: failures.is_empty(),
+            matched,
+            failures,
+        });
+    }
+
+    let passed = cases.iter().filter(|c| c.passed).count();
+    Ok(SuiteResult {
+        engine: re.name(),
+        passed,
+        failed: cases.len() - passed,
+        cases,
+    })
+}
+
+#[tauri::command]
+pub fn regex_suite_load(path: &str) -> Result<RegexSuite, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+    serde_json::from_str(&content).map_err(|e| format!("{path}: {e}"))
+}
+
+/// 以格式化的 JSON 保存，便于提交到仓库和代码评审
+#[tauri::command]
+pub fn regex_suite_save(path: &str, suite: RegexSuite) -> Result<(), String> {
+    let mut content = serde_json::to_string_pretty(&suite).map_err(|e| format!("{e:?}"))?;
+    content.push('\n');
+    std::fs::write(path, content).map_err(|e| format!("{path}: {e}"))
+}
+
+fn describe(value: &Option<String>) -> String {
+    match value {
+        Some(value) => format!("{value:?}"),
+        None => "no participation".to_string(),
+    }
+}
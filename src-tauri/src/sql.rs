@@ -0,0 +1,881 @@
+use serde_json::Value;
+
+/// 格式化 SQL
+///
+/// `dialect` 取 `ansi`（默认）、`postgresql`、`mysql`、`sqlite`，决定引号、注释和占位符的识别方式；
+/// `keyword_case` 取 `upper`、`lower`、`preserve`（默认）；`minify` 为 true 时压缩成一行。
+/// `params` 为 JSON 数组时按顺序替换 `?`，并按序号替换 `$1`/`?1`/`:1`；为 JSON 对象时替换
+/// `:name`/`@name`/`$name`，方便把日志里的参数填回语句。PostgreSQL 中 `?` 是 jsonb 运算符，不作占位符。
+#[tauri::command]
+pub fn sql_format(
+    input: &str,
+    dialect: Option<String>,
+    keyword_case: Option<String>,
+    indent: Option<usize>,
+    minify: Option<bool>,
+    params: Option<Value>,
+) -> Result<String, String> {
+    let dialect = match dialect.as_deref().map(|d| d.to_ascii_lowercase()) {
+        None => Dialect::Ansi,
+        Some(d) => match d.as_str() {
+            "" | "ansi" | "sql" => Dialect::Ansi,
+            "postgresql" | "postgres" | "pg" => Dialect::PostgreSql,
+            "mysql" | "mariadb" => Dialect::MySql,
+            "sqlite" => Dialect::Sqlite,
+            _ => return Err(format!("unknown sql dialect: {d}")),
+        },
+    };
+    let keyword_case = match keyword_case.as_deref().unwrap_or("preserve") {
+        "upper" => KeywordCase::Upper,
+        "lower" => KeywordCase::Lower,
+        "preserve" => KeywordCase::Preserve,
+        other => return Err(format!("unknown keyword case: {other}")),
+    };
+
+    let mut tokens = tokenize(input, dialect)?;
+    if let Some(params) = &params {
+        substitute_params(&mut tokens, params, dialect)?;
+    }
+    let tokens = merge_phrases(tokens);
+
+    let formatter = Formatter {
+        keyword_case,
+        indent: " ".repeat(indent.unwrap_or(2)),
+        minify: minify.unwrap_or(false),
+    };
+
+    Ok(formatter.format(&tokens))
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Dialect {
+    Ansi,
+    PostgreSql,
+    MySql,
+    Sqlite,
+}
+
+#[derive(Clone, Copy)]
+enum KeywordCase {
+    Upper,
+    Lower,
+    Preserve,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum TokenKind {
+    Word,
+    QuotedIdent,
+    String,
+    Number,
+    Placeholder,
+    Operator,
+    OpenParen,
+    CloseParen,
+    Comma,
+    Semicolon,
+    Dot,
+    /// 后面紧跟括号、用作函数名的关键字，如 `REPLACE(`、`LEFT(`
+    Function,
+    LineComment,
+    BlockComment,
+}
+
+#[derive(Clone, Debug)]
+struct Token {
+    kind: TokenKind,
+    text: String,
+}
+
+fn tokenize(input: &str, dialect: Dialect) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+
+    let take_quoted = |start: usize, close: char, backslash: bool| -> Result<usize, String> {
+        let mut j = start + 1;
+        while j < chars.len() {
+            if backslash && chars[j] == '\\' {
+                j += 2;
+                continue;
+            }
+            if chars[j] == close {
+                // 连续两个引号是转义
+                if chars.get(j + 1) == Some(&close) {
+                    j += 2;
+                    continue;
+                }
+                return Ok(j + 1);
+            }
+            j += 1;
+        }
+        let line = chars[..start].iter().filter(|c| **c == '\n').count() + 1;
+        Err(format!(
+            "unterminated {close} quote starting at line {line}"
+        ))
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let start = i;
+
+        let kind = if c.is_whitespace() {
+            i += 1;
+            continue;
+        } else if (c == '-' && next == Some('-')) || (c == '#' && dialect == Dialect::MySql) {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            TokenKind::LineComment
+        } else if c == '/' && next == Some('*') {
+            i = match (i + 2..chars.len().saturating_sub(1))
+                .find(|&j| chars[j] == '*' && chars[j + 1] == '/')
+            {
+                Some(j) => j + 2,
+                None => return Err("unterminated block comment".to_string()),
+            };
+            TokenKind::BlockComment
+        } else if c == '\'' {
+            i = take_quoted(i, '\'', dialect == Dialect::MySql)?;
+            TokenKind::String
+        } else if matches!(c, 'E' | 'e') && next == Some('\'') && dialect == Dialect::PostgreSql {
+            i = take_quoted(i + 1, '\'', true)?;
+            TokenKind::String
+        } else if matches!(c, 'N' | 'n' | 'X' | 'x' | 'B' | 'b') && next == Some('\'') {
+            i = take_quoted(i + 1, '\'', dialect == Dialect::MySql)?;
+            TokenKind::String
+        } else if c == '"' {
+            if dialect == Dialect::MySql {
+                i = take_quoted(i, '"', true)?;
+                TokenKind::String
+            } else {
+                i = take_quoted(i, '"', false)?;
+                TokenKind::QuotedIdent
+            }
+        } else if c == '`' && matches!(dialect, Dialect::MySql | Dialect::Sqlite) {
+            i = take_quoted(i, '`', false)?;
+            TokenKind::QuotedIdent
+        } else if c == '[' && dialect == Dialect::Sqlite {
+            i = take_quoted(i, ']', false)?;
+            TokenKind::QuotedIdent
+        } else if c == '$' && dialect == Dialect::PostgreSql && dollar_tag(&chars, i).is_some() {
+            // $tag$ ... $tag$
+            let tag = dollar_tag(&chars, i).unwrap_or_default();
+            let body = i + tag.len();
+            i = match (body..chars.len()).find(|&j| chars[j..].starts_with(&tag)) {
+                Some(j) => j + tag.len(),
+                None => {
+                    return Err(format!(
+                        "unterminated dollar-quoted string {}",
+                        tag.iter().collect::<String>()
+                    ))
+                }
+            };
+            TokenKind::String
+        } else if c.is_ascii_digit() || (c == '.' && next.is_some_and(|n| n.is_ascii_digit())) {
+            i += 1;
+            if c == '0' && matches!(next, Some('x' | 'X')) {
+                i += 1;
+                while i < chars.len() && chars[i].is_ascii_hexdigit() {
+                    i += 1;
+                }
+            } else {
+                while i < chars.len() {
+                    let d = chars[i];
+                    if d.is_ascii_digit() || d == '.' {
+                        i += 1;
+                    } else if matches!(d, 'e' | 'E')
+                        && chars
+                            .get(i + 1)
+                            .is_some_and(|n| n.is_ascii_digit() || *n == '-' || *n == '+')
+                    {
+                        i += 2;
+                    } else {
+                        break;
+                    }
+                }
+            }
+            TokenKind::Number
+        } else if (c == '?' && dialect != Dialect::PostgreSql)
+            || (c == '$' && next.is_some_and(|n| n.is_ascii_digit()))
+        {
+            i += 1;
+            while i < chars.len() && chars[i].is_ascii_digit() {
+                i += 1;
+            }
+            TokenKind::Placeholder
+        } else if (c == ':' && next.is_some_and(is_word_char))
+            || (c == '@' && dialect != Dialect::MySql && next.is_some_and(is_word_start))
+            || (c == '$' && dialect == Dialect::Sqlite && next.is_some_and(is_word_start))
+        {
+            i += 1;
+            while i < chars.len() && is_word_char(chars[i]) {
+                i += 1;
+            }
+            TokenKind::Placeholder
+        } else if is_word_start(c) || (c == '@' && dialect == Dialect::MySql) {
+            i += 1;
+            while i < chars.len() && (is_word_char(chars[i]) || chars[i] == '$') {
+                i += 1;
+            }
+            TokenKind::Word
+        } else if c == '(' {
+            i += 1;
+            TokenKind::OpenParen
+        } else if c == ')' {
+            i += 1;
+            TokenKind::CloseParen
+        } else if c == ',' {
+            i += 1;
+            TokenKind::Comma
+        } else if c == ';' {
+            i += 1;
+            TokenKind::Semicolon
+        } else if c == '.' {
+            i += 1;
+            TokenKind::Dot
+        } else {
+            const OPERATORS: [&str; 18] = [
+                "<=>", "->>", "#>>", "::", "<=", ">=", "<>", "!=", "||", "->", "#>", "@>", "<@",
+                ":=", "<<", ">>", "?|", "?&",
+            ];
+            let rest: String = chars[i..chars.len().min(i + 3)].iter().collect();
+            i += OPERATORS
+                .iter()
+                .find(|op| rest.starts_with(*op))
+                .map_or(1, |op| op.len());
+            TokenKind::Operator
+        };
+
+        tokens.push(Token {
+            kind,
+            text: chars[start..i].iter().collect(),
+        });
+    }
+
+    Ok(tokens)
+}
+
+fn is_word_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn dollar_tag(chars: &[char], start: usize) -> Option<Vec<char>> {
+    let mut j = start + 1;
+    while j < chars.len()
+        && is_word_char(chars[j])
+        && !(j == start + 1 && chars[j].is_ascii_digit())
+    {
+        j += 1;
+    }
+    (chars.get(j) == Some(&'$')).then(|| chars[start..=j].to_vec())
+}
+
+fn substitute_params(tokens: &mut [Token], params: &Value, dialect: Dialect) -> Result<(), String> {
+    let mut next = 0;
+    for token in tokens
+        .iter_mut()
+        .filter(|t| t.kind == TokenKind::Placeholder)
+    {
+        let key = &token.text[1..];
+        let value = match params {
+            Value::Array(values) if token.text == "?" => {
+                next += 1;
+                values.get(next - 1)
+            }
+            Value::Array(values) => match key.parse::<usize>() {
+                Ok(n) if n > 0 => values.get(n - 1),
+                _ => None,
+            },
+            Value::Object(values) => values.get(key),
+            _ => return Err("params must be a JSON array or object".to_string()),
+        };
+
+        if let Some(value) = value {
+            token.text = sql_literal(value);
+            // MySQL 字符串里的反斜杠是转义符
+            if dialect == Dialect::MySql {
+                token.text = token.text.replace('\\', "\\\\");
+            }
+            token.kind = match value {
+                Value::Number(_) => TokenKind::Number,
+                Value::Bool(_) | Value::Null => TokenKind::Word,
+                _ => TokenKind::String,
+            };
+        }
+    }
+
+    Ok(())
+}
+
+pub fn sql_literal(value: &Value) -> String {
+    match value {
+        Value::Null => "NULL".to_string(),
+        Value::Bool(b) => if *b { "TRUE" } else { "FALSE" }.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => format!("'{}'", s.replace('\'', "''")),
+        other => format!("'{}'", other.to_string().replace('\'', "''")),
+    }
+}
+
+/// 多词关键字，按从长到短匹配
+const PHRASES: [&str; 31] = [
+    "ON DUPLICATE KEY UPDATE",
+    "LEFT OUTER JOIN",
+    "RIGHT OUTER JOIN",
+    "FULL OUTER JOIN",
+    "INSERT OR REPLACE INTO",
+    "INSERT IGNORE INTO",
+    "INSERT INTO",
+    "REPLACE INTO",
+    "DELETE FROM",
+    "GROUP BY",
+    "ORDER BY",
+    "PARTITION BY",
+    "UNION ALL",
+    "LEFT JOIN",
+    "RIGHT JOIN",
+    "INNER JOIN",
+    "FULL JOIN",
+    "CROSS JOIN",
+    "NATURAL JOIN",
+    "ON CONFLICT",
+    "DO UPDATE SET",
+    "IS NOT",
+    "NOT IN",
+    "NOT LIKE",
+    "NOT BETWEEN",
+    "NOT EXISTS",
+    "IS DISTINCT FROM",
+    "FETCH FIRST",
+    "FOR UPDATE",
+    "CREATE TABLE",
+    "CHARACTER SET",
+];
+
+/// 另起一行、内容缩进的子句关键字
+const CLAUSES: [&str; 28] = [
+    "SELECT",
+    "FROM",
+    "WHERE",
+    "GROUP BY",
+    "ORDER BY",
+    "HAVING",
+    "LIMIT",
+    "OFFSET",
+    "UNION",
+    "UNION ALL",
+    "INTERSECT",
+    "EXCEPT",
+    "INSERT INTO",
+    "INSERT IGNORE INTO",
+    "INSERT OR REPLACE INTO",
+    "REPLACE INTO",
+    "VALUES",
+    "UPDATE",
+    "SET",
+    "DELETE FROM",
+    "RETURNING",
+    "WITH",
+    "WINDOW",
+    "ON CONFLICT",
+    "ON DUPLICATE KEY UPDATE",
+    "DO UPDATE SET",
+    "FETCH FIRST",
+    "CREATE TABLE",
+];
+
+/// 在子句内容层级另起一行的关键字
+const NEWLINE_WORDS: [&str; 11] = [
+    "AND",
+    "OR",
+    "JOIN",
+    "LEFT JOIN",
+    "RIGHT JOIN",
+    "INNER JOIN",
+    "FULL JOIN",
+    "CROSS JOIN",
+    "NATURAL JOIN",
+    "LEFT OUTER JOIN",
+    "RIGHT OUTER JOIN",
+];
+
+const KEYWORDS: [&str; 93] = [
+    "ALL",
+    "ALTER",
+    "AND",
+    "ANY",
+    "AS",
+    "ASC",
+    "BEGIN",
+    "BETWEEN",
+    "BY",
+    "CASCADE",
+    "CASE",
+    "CAST",
+    "CHARACTER",
+    "CHECK",
+    "COLLATE",
+    "COLUMN",
+    "COMMIT",
+    "CONSTRAINT",
+    "CREATE",
+    "CROSS",
+    "CURRENT",
+    "DATABASE",
+    "DEFAULT",
+    "DELETE",
+    "DESC",
+    "DISTINCT",
+    "DO",
+    "DROP",
+    "ELSE",
+    "END",
+    "ESCAPE",
+    "EXCEPT",
+    "EXISTS",
+    "FALSE",
+    "FETCH",
+    "FIRST",
+    "FOR",
+    "FOREIGN",
+    "FROM",
+    "FULL",
+    "GROUP",
+    "HAVING",
+    "IF",
+    "IGNORE",
+    "ILIKE",
+    "IN",
+    "INDEX",
+    "INNER",
+    "INSERT",
+    "INTERSECT",
+    "INTO",
+    "IS",
+    "JOIN",
+    "KEY",
+    "LEFT",
+    "LIKE",
+    "LIMIT",
+    "NATURAL",
+    "NEXT",
+    "NOT",
+    "NOTHING",
+    "NULL",
+    "OFFSET",
+    "ON",
+    "ONLY",
+    "OR",
+    "ORDER",
+    "OUTER",
+    "OVER",
+    "PARTITION",
+    "PRIMARY",
+    "REFERENCES",
+    "REPLACE",
+    "RETURNING",
+    "RIGHT",
+    "ROLLBACK",
+    "ROW",
+    "ROWS",
+    "SELECT",
+    "SET",
+    "TABLE",
+    "THEN",
+    "TRUE",
+    "UNION",
+    "UNIQUE",
+    "UPDATE",
+    "USING",
+    "VALUES",
+    "VIEW",
+    "WHEN",
+    "WHERE",
+    "WINDOW",
+    "WITH",
+];
+
+/// 同时也是函数名的关键字，后面紧跟括号时按函数调用处理
+const FUNCTION_KEYWORDS: [&str; 6] = ["CAST", "IF", "INSERT", "LEFT", "REPLACE", "RIGHT"];
+
+fn merge_phrases(tokens: Vec<Token>) -> Vec<Token> {
+    let mut merged: Vec<Token> = vec![];
+    let mut i = 0;
+
+    'outer: while i < tokens.len() {
+        if tokens[i].kind == TokenKind::Word {
+            for phrase in PHRASES {
+                let words: Vec<&str> = phrase.split(' ').collect();
+                let matched = words.iter().enumerate().all(|(n, w)| {
+                    tokens.get(i + n).is_some_and(|t| {
+                        t.kind == TokenKind::Word && t.text.eq_ignore_ascii_case(w)
+                    })
+                });
+                if matched {
+                    let text = tokens[i..i + words.len()]
+                        .iter()
+                        .map(|t| t.text.as_str())
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    merged.push(Token {
+                        kind: TokenKind::Word,
+                        text,
+                    });
+                    i += words.len();
+                    continue 'outer;
+                }
+            }
+        }
+        let mut token = tokens[i].clone();
+        if is_one_of(&token, &FUNCTION_KEYWORDS)
+            && tokens
+                .get(i + 1)
+                .is_some_and(|t| t.kind == TokenKind::OpenParen)
+        {
+            token.kind = TokenKind::Function;
+        }
+        merged.push(token);
+        i += 1;
+    }
+
+    merged
+}
+
+fn is_keyword(token: &Token) -> bool {
+    token.kind == TokenKind::Word
+        && token
+            .text
+            .split(' ')
+            .all(|w| KEYWORDS.contains(&w.to_ascii_uppercase().as_str()))
+}
+
+fn is_one_of(token: &Token, list: &[&str]) -> bool {
+    token.kind == TokenKind::Word && list.iter().any(|w| token.text.eq_ignore_ascii_case(w))
+}
+
+/// 括号内容不超过该长度且不含子查询时保持单行
+const MAX_INLINE_PAREN: usize = 50;
+
+struct Formatter {
+    keyword_case: KeywordCase,
+    indent: String,
+    minify: bool,
+}
+
+struct Block {
+    base: usize,
+    in_clause: bool,
+    inline: bool,
+}
+
+impl Formatter {
+    fn format(&self, tokens: &[Token]) -> String {
+        let mut out = String::new();
+        let mut blocks = vec![Block {
+            base: 0,
+            in_clause: false,
+            inline: false,
+        }];
+        let mut between = false;
+        let mut prev: Option<&Token> = None;
+
+        for (i, token) in tokens.iter().enumerate() {
+            if self.minify && matches!(token.kind, TokenKind::LineComment | TokenKind::BlockComment)
+            {
+                continue;
+            }
+
+            let block = blocks.last().unwrap_or(&blocks[0]);
+            let inline = self.minify || blocks.iter().any(|b| b.inline);
+            let content = block.base + usize::from(block.in_clause);
+            let text = self.render(token);
+
+            match token.kind {
+                TokenKind::Word if !inline && is_one_of(token, &CLAUSES) => {
+                    let base = block.base;
+                    if prev.is_some_and(|p| p.kind != TokenKind::OpenParen) {
+                        self.newline(&mut out, base);
+                    }
+                    out.push_str(&text);
+                    if let Some(block) = blocks.last_mut() {
+                        block.in_clause = true;
+                    }
+                    self.newline(&mut out, base + 1);
+                    prev = Some(token);
+                    continue;
+                }
+                TokenKind::Word if !inline && is_one_of(token, &NEWLINE_WORDS) => {
+                    if token.text.eq_ignore_ascii_case("AND") && between {
+                        between = false;
+                    } else {
+                        self.newline(&mut out, content);
+                        out.push_str(&text);
+                        prev = Some(token);
+                        continue;
+                    }
+                }
+                TokenKind::Word if is_one_of(token, &["BETWEEN", "NOT BETWEEN"]) => {
+                    between = true;
+                }
+                TokenKind::OpenParen => {
+                    let subquery = tokens
+                        .get(i + 1)
+                        .is_some_and(|t| is_one_of(t, &["SELECT", "WITH"]));
+                    let short = paren_len(tokens, i).is_some_and(|n| n <= MAX_INLINE_PAREN);
+                    self.space(&mut out, prev, token);
+                    if table_columns(tokens, i) && !out.ends_with(' ') {
+                        out.push(' ');
+                    }
+                    out.push('(');
+                    if inline || (short && !subquery) {
+                        blocks.push(Block {
+                            base: content,
+                            in_clause: false,
+                            inline: true,
+                        });
+                    } else {
+                        blocks.push(Block {
+                            base: content + 1,
+                            in_clause: false,
+                            inline: false,
+                        });
+                        self.newline(&mut out, content + 1);
+                    }
+                    prev = Some(token);
+                    continue;
+                }
+                TokenKind::CloseParen => {
+                    if let Some(closed) = blocks.pop() {
+                        if blocks.is_empty() {
+                            blocks.push(closed);
+                        } else if !closed.inline {
+                            self.newline(&mut out, closed.base - 1);
+                        }
+                    }
+                    out.push(')');
+                    prev = Some(token);
+                    continue;
+                }
+                TokenKind::Comma if !inline => {
+                    out.push(',');
+                    self.newline(&mut out, content);
+                    prev = Some(token);
+                    continue;
+                }
+                TokenKind::Semicolon => {
+                    out.push(';');
+                    blocks.truncate(1);
+                    blocks[0].in_clause = false;
+                    if !self.minify && i + 1 < tokens.len() {
+                        self.newline(&mut out, 0);
+                        out.push('\n');
+                    }
+                    prev = Some(token);
+                    continue;
+                }
+                TokenKind::LineComment => {
+                    self.space(&mut out, prev, token);
+                    out.push_str(&text);
+                    self.newline(&mut out, content);
+                    prev = Some(token);
+                    continue;
+                }
+                _ => {}
+            }
+
+            self.space(&mut out, prev, token);
+            out.push_str(&text);
+            prev = Some(token);
+        }
+
+        out.trim_end().to_string()
+    }
+
+    fn render(&self, token: &Token) -> String {
+        if !is_keyword(token) {
+            return token.text.to_owned();
+        }
+        match self.keyword_case {
+            KeywordCase::Upper => token.text.to_uppercase(),
+            KeywordCase::Lower => token.text.to_lowercase(),
+            KeywordCase::Preserve => token.text.to_owned(),
+        }
+    }
+
+    fn newline(&self, out: &mut String, level: usize) {
+        if self.minify {
+            return;
+        }
+        // 已经在行首时只重设缩进
+        let trimmed = out.trim_end_matches([' ', '\t']).len();
+        out.truncate(trimmed);
+        if !out.is_empty() && !out.ends_with('\n') {
+            out.push('\n');
+        }
+        out.push_str(&self.indent.repeat(level));
+    }
+
+    fn space(&self, out: &mut String, prev: Option<&Token>, token: &Token) {
+        let Some(prev) = prev else {
+            return;
+        };
+        if out.ends_with(|c: char| c.is_whitespace()) {
+            return;
+        }
+
+        let glued_before = matches!(
+            token.kind,
+            TokenKind::Comma | TokenKind::CloseParen | TokenKind::Dot | TokenKind::Semicolon
+        ) || (token.kind == TokenKind::Operator && token.text == "::");
+        let glued_after = matches!(prev.kind, TokenKind::OpenParen | TokenKind::Dot)
+            || (prev.kind == TokenKind::Operator && prev.text == "::");
+        // 函数调用的括号紧跟函数名
+        let call = token.kind == TokenKind::OpenParen
+            && (prev.kind == TokenKind::Function
+                || (matches!(prev.kind, TokenKind::Word | TokenKind::QuotedIdent)
+                    && !is_keyword(prev)));
+
+        if !(glued_before || glued_after || call) {
+            out.push(' ');
+        }
+    }
+}
+
+/// `INSERT INTO t (a, b)` 这类表名后的列清单，不是函数调用
+fn table_columns(tokens: &[Token], open: usize) -> bool {
+    let name = tokens[..open].iter().rev().take_while(|t| {
+        matches!(
+            t.kind,
+            TokenKind::Word | TokenKind::QuotedIdent | TokenKind::Dot
+        ) && !is_keyword(t)
+    });
+    let keyword = tokens[..open].iter().rev().nth(name.count());
+    keyword.is_some_and(|t| {
+        is_one_of(
+            t,
+            &[
+                "INSERT INTO",
+                "INSERT IGNORE INTO",
+                "INSERT OR REPLACE INTO",
+                "REPLACE INTO",
+                "CREATE TABLE",
+            ],
+        )
+    })
+}
+
+/// 括号内（不含括号本身）单行渲染的长度，括号不配对时返回 None
+fn paren_len(tokens: &[Token], open: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut len = 0;
+    for token in &tokens[open..] {
+        match token.kind {
+            TokenKind::OpenParen => depth += 1,
+            TokenKind::CloseParen => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(len);
+                }
+            }
+            TokenKind::LineComment => return None,
+            _ => {}
+        }
+        len += token.text.len() + 1;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(input: &str, dialect: Dialect) -> Vec<(TokenKind, String)> {
+        tokenize(input, dialect)
+            .unwrap()
+            .into_iter()
+            .map(|t| (t.kind, t.text))
+            .collect()
+    }
+
+    #[test]
+    fn quoted_identifiers() {
+        use TokenKind::*;
+        assert_eq!(
+            tokens(r#"select "a ""b""" from t"#, Dialect::Ansi)[1],
+            (QuotedIdent, r#""a ""b""""#.to_string())
+        );
+        assert_eq!(
+            tokens(r#"`a b` "c\"d""#, Dialect::MySql),
+            [
+                (QuotedIdent, "`a b`".to_string()),
+                (String, r#""c\"d""#.to_string()),
+            ]
+        );
+        assert_eq!(
+            tokens("[a b]", Dialect::Sqlite),
+            [(QuotedIdent, "[a b]".to_string())]
+        );
+        assert!(tokenize(r#""abc"#, Dialect::Ansi).is_err());
+    }
+
+    #[test]
+    fn dollar_quoted_strings() {
+        use TokenKind::*;
+        assert_eq!(
+            tokens("select $$a 'b' $1$$, $fn$ $$ $fn$", Dialect::PostgreSql),
+            [
+                (Word, "select".to_string()),
+                (String, "$$a 'b' $1$$".to_string()),
+                (Comma, ",".to_string()),
+                (String, "$fn$ $$ $fn$".to_string()),
+            ]
+        );
+        assert!(tokenize("$x$ abc", Dialect::PostgreSql).is_err());
+    }
+
+    #[test]
+    fn comments() {
+        use TokenKind::*;
+        assert_eq!(
+            tokens("a -- x\n/* y\n*/ # z", Dialect::MySql),
+            [
+                (Word, "a".to_string()),
+                (LineComment, "-- x".to_string()),
+                (BlockComment, "/* y\n*/".to_string()),
+                (LineComment, "# z".to_string()),
+            ]
+        );
+        // 只有 MySQL 把 `#` 当作注释
+        assert_eq!(tokens("#", Dialect::Ansi), [(Operator, "#".to_string())]);
+        assert!(tokenize("/* x", Dialect::Ansi).is_err());
+    }
+
+    #[test]
+    fn placeholders() {
+        let placeholders = |input: &str, dialect: Dialect| {
+            tokens(input, dialect)
+                .into_iter()
+                .filter(|(kind, _)| *kind == TokenKind::Placeholder)
+                .map(|(_, text)| text)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            placeholders("? ?2 $1 :name @id", Dialect::Ansi),
+            ["?", "?2", "$1", ":name", "@id"]
+        );
+        assert_eq!(placeholders("$name @@x", Dialect::Sqlite), ["$name", "@x"]);
+        // PostgreSQL 的 `?` 是 jsonb 运算符，`::` 是类型转换
+        assert_eq!(
+            placeholders("a ? 'k' and a ?| b and $2::int", Dialect::PostgreSql),
+            ["$2"]
+        );
+        // MySQL 的 `@name` 是用户变量
+        assert_eq!(placeholders("@var = ?", Dialect::MySql), ["?"]);
+    }
+}
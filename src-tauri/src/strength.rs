@@ -0,0 +1,680 @@
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use passwords::analyzer;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+
+/// 与 zxcvbn 相同，只对前 100 个字符做模式识别，其余字符按字符集暴力猜测
+const MAX_ANALYSED: usize = 100;
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct StrengthOptions {
+    /// k-anonymity 前缀文件（Have I Been Pwned range 接口的响应，每行 `SUFFIX:COUNT`），
+    /// 或者按前缀命名（`ABCDE`、`ABCDE.txt`）存放这些文件的目录
+    pub prefix_file: Option<String>,
+    /// 额外的常见密码列表，每行一个
+    pub common_list: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct PatternMatch {
+    /// `dictionary`、`keyboard`、`sequence`、`repeat`、`date`
+    pub kind: &'static str,
+    pub token: String,
+    /// 字符偏移
+    pub start: usize,
+    pub end: usize,
+    pub guesses_log10: f64,
+    pub detail: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CrackTime {
+    pub scenario: &'static str,
+    pub guesses_per_second: f64,
+    pub seconds: f64,
+    pub display: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct BreachCheck {
+    pub prefix: String,
+    /// 泄露次数，0 表示前缀文件中没有该密码
+    pub count: u64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct PasswordStrength {
+    pub length: usize,
+    /// 按字符集大小估计的熵（bit）
+    pub entropy: f64,
+    /// 按识别出的模式估计的猜测次数（log10）
+    pub guesses_log10: f64,
+    /// 0~4，与 zxcvbn 的分档相同
+    pub score: u8,
+    /// passwords crate 的评分，0~100
+    pub passwords_score: f64,
+    pub common: bool,
+    pub breach: Option<BreachCheck>,
+    pub patterns: Vec<PatternMatch>,
+    pub crack_times: Vec<CrackTime>,
+    pub feedback: Vec<String>,
+}
+
+/// 评估已有密码的强度：模式识别、破解时间估计、常见密码和泄露前缀文件检查
+///
+/// 计算在阻塞线程池中执行。
+#[tauri::command]
+pub async fn password_strength(
+    password: String,
+    options: Option<StrengthOptions>,
+) -> Result<PasswordStrength, String> {
+    let options = options.unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || strength(&password, &options))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+fn strength(password: &str, options: &StrengthOptions) -> Result<PasswordStrength, String> {
+    let chars = password.chars().collect::<Vec<_>>();
+    let analysed = &chars[..chars.len().min(MAX_ANALYSED)];
+
+    let common_list = match &options.common_list {
+        Some(path) if !path.is_empty() => std::fs::read_to_string(path)
+            .map_err(|e| format!("{path}: {e}"))?
+            .lines()
+            .map(|l| l.trim().to_lowercase())
+            .filter(|l| !l.is_empty())
+            .collect::<HashSet<_>>(),
+        _ => HashSet::new(),
+    };
+    let is_common = |word: &str| analyzer::is_common_password(word) || common_list.contains(word);
+    let common = is_common(password) || is_common(&password.to_lowercase());
+
+    let mut candidates = vec![];
+    if common && chars.len() <= MAX_ANALYSED {
+        candidates.push(PatternMatch {
+            kind: "dictionary",
+            token: String::new(),
+            start: 0,
+            end: chars.len(),
+            guesses_log10: 3.0,
+            detail: "the whole password is a common password".to_string(),
+        });
+    }
+    dictionary_matches(analysed, &is_common, &mut candidates);
+    keyboard_matches(analysed, &mut candidates);
+    sequence_matches(analysed, &mut candidates);
+    repeat_matches(analysed, &mut candidates);
+    date_matches(analysed, &mut candidates);
+
+    let (mut guesses_log10, patterns) = most_guessable(analysed, candidates);
+    guesses_log10 += chars[analysed.len()..]
+        .iter()
+        .map(|&c| cardinality(c).log10())
+        .sum::<f64>();
+    let score = match guesses_log10 {
+        g if g < 3.0 => 0,
+        g if g < 6.0 => 1,
+        g if g < 8.0 => 2,
+        g if g < 10.0 => 3,
+        _ => 4,
+    };
+
+    let breach = match &options.prefix_file {
+        Some(path) if !path.is_empty() => Some(breach_check(password, path)?),
+        _ => None,
+    };
+
+    let crack_times = [
+        ("online, throttled (100/hour)", 100.0 / 3600.0),
+        ("online, unthrottled (10/s)", 10.0),
+        ("offline, slow hash (1e4/s)", 1e4),
+        ("offline, fast hash (1e10/s)", 1e10),
+    ]
+    .into_iter()
+    .map(|(scenario, rate)| {
+        let seconds = 10f64.powf(guesses_log10) / rate;
+        CrackTime {
+            scenario,
+            guesses_per_second: rate,
+            seconds,
+            display: display_time(seconds),
+        }
+    })
+    .collect();
+
+    let feedback = feedback(&chars, score, common, &breach, &patterns);
+    Ok(PasswordStrength {
+        length: chars.len(),
+        entropy: charset_entropy(&chars),
+        guesses_log10,
+        score,
+        passwords_score: passwords::scorer::score(&analyzer::analyze(password)),
+        common,
+        breach,
+        patterns,
+        crack_times,
+        feedback,
+    })
+}
+
+fn cardinality(c: char) -> f64 {
+    match c {
+        '0'..='9' => 10.0,
+        'a'..='z' | 'A'..='Z' => 26.0,
+        c if c.is_ascii() => 33.0,
+        _ => 100.0,
+    }
+}
+
+fn charset_entropy(chars: &[char]) -> f64 {
+    let mut pool = 0.0;
+    for (test, size) in [
+        (char::is_ascii_digit as fn(&char) -> bool, 10.0),
+        (char::is_ascii_lowercase, 26.0),
+        (char::is_ascii_uppercase, 26.0),
+        (|c: &char| c.is_ascii() && !c.is_ascii_alphanumeric(), 33.0),
+        (|c: &char| !c.is_ascii(), 100.0),
+    ] {
+        if chars.iter().any(test) {
+            pool += size;
+        }
+    }
+    if pool == 0.0 {
+        return 0.0;
+    }
+    chars.len() as f64 * f64::log2(pool)
+}
+
+fn l33t(c: char, alternative: bool) -> Option<char> {
+    match c {
+        '4' | '@' => Some('a'),
+        '8' => Some('b'),
+        '(' | '{' | '[' | '<' => Some('c'),
+        '3' => Some('e'),
+        '6' | '9' => Some('g'),
+        '1' | '!' | '|' if alternative => Some('l'),
+        '1' | '!' | '|' => Some('i'),
+        '0' => Some('o'),
+        '$' | '5' => Some('s'),
+        '7' | '+' => Some('t'),
+        '%' => Some('x'),
+        '2' => Some('z'),
+        _ => None,
+    }
+}
+
+/// 4~12 个字母的子串（含 l33t 还原）在常见密码列表中即视为字典词
+fn dictionary_matches(
+    chars: &[char],
+    is_common: &dyn Fn(&str) -> bool,
+    candidates: &mut Vec<PatternMatch>,
+) {
+    for start in 0..chars.len() {
+        for end in start + 4..=chars.len().min(start + 12) {
+            let token = &chars[start..end];
+            if !token.iter().any(|c| c.is_ascii_alphabetic()) {
+                continue;
+            }
+            for alternative in [false, true] {
+                let mut subs = 0;
+                let word = token
+                    .iter()
+                    .map(|&c| match l33t(c, alternative) {
+                        Some(plain) => {
+                            subs += 1;
+                            plain
+                        }
+                        None => c.to_ascii_lowercase(),
+                    })
+                    .collect::<String>();
+                if !word.chars().all(|c| c.is_ascii_lowercase()) || !is_common(&word) {
+                    continue;
+                }
+                if alternative && subs == 0 {
+                    continue;
+                }
+
+                let upper = token.iter().filter(|c| c.is_ascii_uppercase()).count();
+                let lower = token.iter().filter(|c| c.is_ascii_lowercase()).count();
+                let case_variations = if upper == 0
+                    || lower == 0
+                    || (upper == 1
+                        && (token[0].is_ascii_uppercase()
+                            || token[token.len() - 1].is_ascii_uppercase()))
+                {
+                    if upper == 0 {
+                        1.0
+                    } else {
+                        2.0
+                    }
+                } else {
+                    (1..=upper.min(lower))
+                        .map(|k| binomial(upper + lower, k))
+                        .sum()
+                };
+                // 没有频率排名，按常见密码列表的中位排名估计
+                let guesses = 1e4 * case_variations * 2f64.powi(subs);
+                let detail = if subs > 0 {
+                    format!("dictionary word {word:?} with l33t substitutions")
+                } else {
+                    format!("dictionary word {word:?}")
+                };
+                candidates.push(PatternMatch {
+                    kind: "dictionary",
+                    token: String::new(),
+                    start,
+                    end,
+                    guesses_log10: guesses.log10(),
+                    detail,
+                });
+                break;
+            }
+        }
+    }
+}
+
+fn binomial(n: usize, k: usize) -> f64 {
+    (1..=k).fold(1.0, |acc, i| acc * (n + 1 - i) as f64 / i as f64)
+}
+
+const KEYBOARD: [(&str, &str, f64); 4] = [
+    ("`1234567890-=", "~!@#$%^&*()_+", 0.0),
+    ("qwertyuiop[]\\", "QWERTYUIOP{}|", 1.5),
+    ("asdfghjkl;'", "ASDFGHJKL:\"", 1.75),
+    ("zxcvbnm,./", "ZXCVBNM<>?", 2.25),
+];
+
+/// QWERTY 上的坐标，行错开半个键位左右
+fn key_position(c: char) -> Option<(usize, f64, bool)> {
+    for (row, (plain, shifted, offset)) in KEYBOARD.iter().enumerate() {
+        if let Some(col) = plain.chars().position(|k| k == c) {
+            return Some((row, offset + col as f64, false));
+        }
+        if let Some(col) = shifted.chars().position(|k| k == c) {
+            return Some((row, offset + col as f64, true));
+        }
+    }
+    None
+}
+
+fn adjacent(a: char, b: char) -> bool {
+    match (key_position(a), key_position(b)) {
+        (Some((ra, xa, _)), Some((rb, xb, _))) => {
+            let dx = (xa - xb).abs();
+            (ra == rb && dx == 1.0) || (ra.abs_diff(rb) == 1 && dx <= 0.75)
+        }
+        _ => false,
+    }
+}
+
+fn keyboard_matches(chars: &[char], candidates: &mut Vec<PatternMatch>) {
+    let mut start = 0;
+    while start < chars.len() {
+        let mut end = start + 1;
+        while end < chars.len() && adjacent(chars[end - 1], chars[end]) {
+            end += 1;
+        }
+        if end - start >= 4 {
+            let token = &chars[start..end];
+            let shifted = token
+                .iter()
+                .filter(|&&c| key_position(c).is_some_and(|k| k.2))
+                .count();
+            let mut guesses = 94.0 * 4f64.powi((end - start - 1) as i32);
+            if shifted > 0 && shifted < token.len() {
+                guesses *= 2.0;
+            }
+            candidates.push(PatternMatch {
+                kind: "keyboard",
+                token: String::new(),
+                start,
+                end,
+                guesses_log10: guesses.log10(),
+                detail: "keyboard walk on a QWERTY layout".to_string(),
+            });
+        }
+        start = end.max(start + 1);
+    }
+}
+
+fn sequence_matches(chars: &[char], candidates: &mut Vec<PatternMatch>) {
+    let class = |c: char| {
+        if c.is_ascii_digit() {
+            1
+        } else if c.is_ascii_lowercase() {
+            2
+        } else if c.is_ascii_uppercase() {
+            3
+        } else {
+            0
+        }
+    };
+
+    let mut start = 0;
+    while start + 1 < chars.len() {
+        let delta = chars[start + 1] as i32 - chars[start] as i32;
+        let mut end = start + 1;
+        if delta.abs() == 1 && class(chars[start]) != 0 {
+            while end < chars.len()
+                && class(chars[end]) == class(chars[start])
+                && chars[end] as i32 - chars[end - 1] as i32 == delta
+            {
+                end += 1;
+            }
+        }
+        if end - start >= 3 {
+            let first = chars[start];
+            let base = if matches!(first, 'a' | 'A' | 'z' | 'Z' | '0' | '1' | '9') {
+                4.0
+            } else if first.is_ascii_digit() {
+                10.0
+            } else {
+                26.0
+            };
+            let guesses = base * (end - start) as f64 * if delta < 0 { 2.0 } else { 1.0 };
+            candidates.push(PatternMatch {
+                kind: "sequence",
+                token: String::new(),
+                start,
+                end,
+                guesses_log10: guesses.log10(),
+                detail: if delta < 0 {
+                    "descending sequence".to_string()
+                } else {
+                    "ascending sequence".to_string()
+                },
+            });
+            start = end - 1;
+        } else {
+            start += 1;
+        }
+    }
+}
+
+/// `aaa` 这样的单字符重复，以及 `abcabc` 这样的片段重复
+fn repeat_matches(chars: &[char], candidates: &mut Vec<PatternMatch>) {
+    for start in 0..chars.len() {
+        for len in 1..=(chars.len() - start) / 2 {
+            let unit = &chars[start..start + len];
+            let mut count = 1;
+            while chars[start + count * len..].starts_with(unit) {
+                count += 1;
+            }
+            if count < 2 || (len == 1 && count < 3) {
+                continue;
+            }
+            let end = start + len * count;
+            let unit_guesses: f64 = unit.iter().map(|&c| cardinality(c)).product();
+            candidates.push(PatternMatch {
+                kind: "repeat",
+                token: String::new(),
+                start,
+                end,
+                guesses_log10: (unit_guesses * count as f64).log10(),
+                detail: format!(
+                    "{:?} repeated {count} times",
+                    unit.iter().collect::<String>()
+                ),
+            });
+        }
+    }
+}
+
+fn date_matches(chars: &[char], candidates: &mut Vec<PatternMatch>) {
+    let this_year = 1970
+        + std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs() / 31_556_952) as i64;
+    let year_space = |year: i64| (year - this_year).abs().max(20) as f64;
+    let full_year = |y: i64, digits: usize| match digits {
+        2 if y > (this_year % 100) + 1 => Some(1900 + y),
+        2 => Some(2000 + y),
+        4 if (1900..=2099).contains(&y) => Some(y),
+        _ => None,
+    };
+    let valid = |d: i64, m: i64| (1..=31).contains(&d) && (1..=12).contains(&m);
+    static SEPARATED: OnceLock<Regex> = OnceLock::new();
+    let separated = SEPARATED.get_or_init(|| {
+        Regex::new(r"^(\d{1,4})([\s/\\._-])(\d{1,2})[\s/\\._-](\d{1,4})$")
+            .expect("date pattern is valid")
+    });
+
+    for start in 0..chars.len() {
+        for end in start + 4..=chars.len().min(start + 10) {
+            let token = chars[start..end].iter().collect::<String>();
+            let mut found = None;
+            if token.chars().all(|c| c.is_ascii_digit()) {
+                let n = |r: std::ops::Range<usize>| token[r].parse::<i64>().unwrap_or(0);
+                found = match token.len() {
+                    4 => full_year(n(0..4), 4).map(|y| (y, 1.0, "year")),
+                    6 => [
+                        (n(0..2), n(2..4), n(4..6)),
+                        (n(2..4), n(0..2), n(4..6)),
+                        (n(4..6), n(2..4), n(0..2)),
+                    ]
+                    .into_iter()
+                    .find(|&(d, m, _)| valid(d, m))
+                    .and_then(|(_, _, y)| full_year(y, 2))
+                    .map(|y| (y, 365.0, "date")),
+                    8 => [
+                        (n(0..2), n(2..4), n(4..8)),
+                        (n(2..4), n(0..2), n(4..8)),
+                        (n(6..8), n(4..6), n(0..4)),
+                    ]
+                    .into_iter()
+                    .find(|&(d, m, y)| valid(d, m) && full_year(y, 4).is_some())
+                    .map(|(_, _, y)| (y, 365.0, "date")),
+                    _ => None,
+                };
+            } else if let Some(caps) = separated.captures(&token) {
+                let (a, b, c) = (&caps[1], &caps[3], &caps[4]);
+                let parse = |s: &str| s.parse::<i64>().unwrap_or(0);
+                found = if a.len() == 4 {
+                    full_year(parse(a), 4)
+                        .filter(|_| valid(parse(c), parse(b)))
+                        .map(|y| (y, 365.0 * 4.0, "date"))
+                } else if c.len() == 2 || c.len() == 4 {
+                    let (a, b) = (parse(a), parse(b));
+                    full_year(parse(c), c.len())
+                        .filter(|_| valid(a, b) || valid(b, a))
+                        .map(|y| (y, 365.0 * 4.0, "date"))
+                } else {
+                    None
+                };
+            }
+
+            if let Some((year, factor, what)) = found {
+                candidates.push(PatternMatch {
+                    kind: "date",
+                    token: String::new(),
+                    start,
+                    end,
+                    guesses_log10: (year_space(year) * factor).log10(),
+                    detail: format!("{what} around {year}"),
+                });
+            }
+        }
+    }
+}
+
+/// 动态规划选出总猜测次数最少的一组不重叠模式，未覆盖的字符按字符集暴力猜测
+///
+/// 候选模式只记录范围，`token` 在选出后才填入。
+fn most_guessable(chars: &[char], candidates: Vec<PatternMatch>) -> (f64, Vec<PatternMatch>) {
+    let n = chars.len();
+    if n == 0 {
+        return (0.0, vec![]);
+    }
+
+    // best[i]：覆盖前 i 个字符的最小 log10 猜测次数，以及最后一段所用的模式
+    let mut best: Vec<(f64, Option<usize>)> = vec![(f64::INFINITY, None); n + 1];
+    best[0] = (0.0, None);
+    for i in 1..=n {
+        let brute = best[i - 1].0 + cardinality(chars[i - 1]).log10();
+        best[i] = (brute, None);
+        for (index, m) in candidates.iter().enumerate() {
+            if m.end == i && best[m.start].0 + m.guesses_log10 < best[i].0 {
+                best[i] = (best[m.start].0 + m.guesses_log10, Some(index));
+            }
+        }
+    }
+
+    let mut chosen = vec![];
+    let mut segments = 0;
+    let mut i = n;
+    let mut in_gap = false;
+    while i > 0 {
+        match best[i].1 {
+            Some(index) => {
+                chosen.push(index);
+                segments += 1;
+                in_gap = false;
+                i = candidates[index].start;
+            }
+            None => {
+                if !in_gap {
+                    segments += 1;
+                }
+                in_gap = true;
+                i -= 1;
+            }
+        }
+    }
+
+    // 与 zxcvbn 相同，多段组合时乘以段数的阶乘
+    let penalty: f64 = (1..=segments).map(|k| (k as f64).log10()).sum();
+    let guesses_log10 = best[n].0 + penalty;
+
+    let mut candidates = candidates.into_iter().map(Some).collect::<Vec<_>>();
+    let mut patterns = chosen
+        .into_iter()
+        .filter_map(|index| candidates[index].take())
+        .map(|m| PatternMatch {
+            token: chars[m.start..m.end].iter().collect(),
+            ..m
+        })
+        .collect::<Vec<_>>();
+    patterns.sort_by_key(|m| m.start);
+    (guesses_log10, patterns)
+}
+
+/// 在 Have I Been Pwned range 格式的前缀文件中查找密码的 SHA-1
+fn breach_check(password: &str, path: &str) -> Result<BreachCheck, String> {
+    let hash = Sha1::digest(password.as_bytes())
+        .iter()
+        .map(|b| format!("{b:02X}"))
+        .collect::<String>();
+    let (prefix, suffix) = hash.split_at(5);
+
+    let file = if Path::new(path).is_dir() {
+        [prefix.to_string(), format!("{prefix}.txt")]
+            .into_iter()
+            .map(|name| Path::new(path).join(name))
+            .find(|p| p.exists())
+            .ok_or_else(|| format!("{path}: no prefix file for {prefix}"))?
+    } else {
+        let file = Path::new(path).to_path_buf();
+        let stem = file
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_uppercase())
+            .unwrap_or_default();
+        if stem.len() == 5 && stem.chars().all(|c| c.is_ascii_hexdigit()) && stem != prefix {
+            return Err(format!(
+                "{path} covers prefix {stem}, but this password's SHA-1 prefix is {prefix}"
+            ));
+        }
+        file
+    };
+
+    let content = std::fs::read_to_string(&file).map_err(|e| format!("{}: {e}", file.display()))?;
+    let mut count = 0;
+    for line in content.lines() {
+        let Some((hash_part, n)) = line.trim().split_once(':') else {
+            continue;
+        };
+        let hash_part = hash_part.trim().to_uppercase();
+        if hash_part == suffix || hash_part == hash {
+            count = n.trim().parse().unwrap_or(1);
+            break;
+        }
+    }
+
+    Ok(BreachCheck {
+        prefix: prefix.to_string(),
+        count,
+    })
+}
+
+fn display_time(seconds: f64) -> String {
+    const UNITS: [(f64, &str); 6] = [
+        (60.0, "second"),
+        (60.0, "minute"),
+        (24.0, "hour"),
+        (30.44, "day"),
+        (12.0, "month"),
+        (100.0, "year"),
+    ];
+    if seconds < 1.0 {
+        return "less than a second".to_string();
+    }
+    let mut value = seconds;
+    for (size, unit) in UNITS {
+        if value < size {
+            let n = value.round() as u64;
+            return format!("{n} {unit}{}", if n == 1 { "" } else { "s" });
+        }
+        value /= size;
+    }
+    "centuries".to_string()
+}
+
+fn feedback(
+    chars: &[char],
+    score: u8,
+    common: bool,
+    breach: &Option<BreachCheck>,
+    patterns: &[PatternMatch],
+) -> Vec<String> {
+    let mut feedback = vec![];
+    if breach.as_ref().is_some_and(|b| b.count > 0) {
+        feedback.push("This password appears in a breach corpus; never use it.".to_string());
+    }
+    if common {
+        feedback.push("This is a very common password.".to_string());
+    }
+    let mut kinds = patterns.iter().map(|m| m.kind).collect::<Vec<_>>();
+    kinds.sort_unstable();
+    kinds.dedup();
+    for kind in kinds {
+        feedback.push(
+            match kind {
+                "dictionary" if patterns.iter().any(|m| m.detail.contains("l33t")) => {
+                    "Predictable substitutions like '@' for 'a' don't help much."
+                }
+                "dictionary" => "Avoid dictionary words and common passwords.",
+                "keyboard" => "Keyboard patterns like qwerty or asdf are easy to guess.",
+                "sequence" => "Avoid sequences like abc or 6543.",
+                "repeat" => "Avoid repeated characters and words like aaa or abcabc.",
+                "date" => "Avoid dates and years that are associated with you.",
+                _ => continue,
+            }
+            .to_string(),
+        );
+    }
+    if score < 3 {
+        if chars.len() < 12 {
+            feedback.push("Use at least 12 characters; a few extra words help most.".to_string());
+        }
+        if chars.iter().all(|c| c.is_ascii_alphabetic()) {
+            feedback.push("Mix in digits or symbols that are not at the start or end.".to_string());
+        }
+    }
+    feedback.dedup();
+    feedback
+}
@@ -0,0 +1,412 @@
+use std::collections::HashSet;
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+use unicode_width::UnicodeWidthStr;
+
+#[derive(Serialize, Debug)]
+pub struct TableOutput {
+    pub delimiter: String,
+    pub quote: String,
+    pub has_header: bool,
+    pub columns: Vec<String>,
+    pub rows: usize,
+    pub output: String,
+}
+
+/// CSV/TSV 转换
+///
+/// 未指定 `delimiter`/`has_header` 时自动探测分隔符、引号和表头。
+/// `output` 取 `json`（默认，对象数组）、`markdown`、`text`（对齐的纯文本表格）；
+/// `infer_types` 为 true 时把数字、布尔和空值转换成对应的 JSON 类型。
+#[tauri::command]
+pub fn csv_convert(
+    input: &str,
+    delimiter: Option<String>,
+    has_header: Option<bool>,
+    output: Option<String>,
+    infer_types: Option<bool>,
+) -> Result<TableOutput, String> {
+    let (sniffed_delimiter, quote) = sniff_dialect(input);
+    let delimiter = match delimiter.as_deref() {
+        None | Some("") => sniffed_delimiter,
+        Some(d) => parse_delimiter(d)?,
+    };
+
+    let mut records = vec![];
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .quote(quote)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(input.as_bytes());
+    for record in reader.records() {
+        let record = record.map_err(|e| csv_error(&e))?;
+        records.push(record.iter().map(|f| f.to_string()).collect::<Vec<_>>());
+    }
+
+    let has_header = has_header.unwrap_or_else(|| sniff_header(&records));
+    let width = records.iter().map(|r| r.len()).max().unwrap_or(0);
+    let mut columns = if has_header && !records.is_empty() {
+        records.remove(0)
+    } else {
+        vec![]
+    };
+    for n in columns.len()..width {
+        columns.push(format!("column{}", n + 1));
+    }
+    dedupe_columns(&mut columns);
+
+    let output = match output.as_deref().unwrap_or("json") {
+        "json" => {
+            let infer_types = infer_types.unwrap_or(false);
+            let objects = records
+                .iter()
+                .map(|record| {
+                    let mut object = Map::new();
+                    for (n, column) in columns.iter().enumerate() {
+                        let field = record.get(n).map_or("", |s| s.as_str());
+                        let value = if infer_types {
+                            infer_value(field)
+                        } else {
+                            Value::String(field.to_string())
+                        };
+                        object.insert(column.to_owned(), value);
+                    }
+                    Value::Object(object)
+                })
+                .collect::<Vec<_>>();
+            serde_json::to_string_pretty(&objects).map_err(|e| format!("{e:?}"))?
+        }
+        "markdown" => markdown_table(&columns, &records),
+        "text" => text_table(&columns, &records),
+        other => return Err(format!("unknown output format: {other}")),
+    };
+
+    Ok(TableOutput {
+        delimiter: (delimiter as char).to_string(),
+        quote: (quote as char).to_string(),
+        has_header,
+        columns,
+        rows: records.len(),
+        output,
+    })
+}
+
+/// JSON 对象数组转 CSV，嵌套对象展开为 `a.b` 形式的列，数组保留为 JSON 文本
+#[tauri::command]
+pub fn json_to_csv(input: &str, delimiter: Option<String>) -> Result<String, String> {
+    let delimiter = match delimiter.as_deref() {
+        None | Some("") => b',',
+        Some(d) => parse_delimiter(d)?,
+    };
+
+    let rows = match serde_json::from_str::<Value>(input).map_err(|e| format!("{e:?}"))? {
+        Value::Array(rows) => rows,
+        row @ Value::Object(_) => vec![row],
+        _ => return Err("input must be a JSON array of objects".to_string()),
+    };
+
+    let mut columns: Vec<String> = vec![];
+    let mut flat_rows = vec![];
+    for row in &rows {
+        let mut flat = Map::new();
+        match row {
+            Value::Object(_) => flatten("", row, &mut flat),
+            other => {
+                flat.insert("value".to_string(), other.clone());
+            }
+        }
+        for key in flat.keys() {
+            if !columns.contains(key) {
+                columns.push(key.to_owned());
+            }
+        }
+        flat_rows.push(flat);
+    }
+
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .from_writer(vec![]);
+    writer.write_record(&columns).map_err(|e| csv_error(&e))?;
+    for flat in &flat_rows {
+        let record = columns.iter().map(|column| match flat.get(column) {
+            None | Some(Value::Null) => String::new(),
+            Some(Value::String(s)) => s.to_owned(),
+            Some(other) => other.to_string(),
+        });
+        writer.write_record(record).map_err(|e| csv_error(&e))?;
+    }
+
+    let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+    Ok(String::from_utf8_lossy(&bytes).to_string())
+}
+
+fn flatten(prefix: &str, value: &Value, flat: &mut Map<String, Value>) {
+    match value {
+        Value::Object(object) if !object.is_empty() => {
+            for (key, value) in object {
+                let key = if prefix.is_empty() {
+                    key.to_owned()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten(&key, value, flat);
+            }
+        }
+        _ => {
+            flat.insert(prefix.to_string(), value.clone());
+        }
+    }
+}
+
+fn parse_delimiter(delimiter: &str) -> Result<u8, String> {
+    match delimiter {
+        "\\t" | "tab" => Ok(b'\t'),
+        d if d.len() == 1 => Ok(d.as_bytes()[0]),
+        d => Err(format!("delimiter must be a single ASCII character: {d}")),
+    }
+}
+
+fn csv_error(e: &csv::Error) -> String {
+    match e.position() {
+        Some(pos) => format!("line {}: {}", pos.line(), e),
+        None => e.to_string(),
+    }
+}
+
+const DELIMITERS: [u8; 4] = [b',', b'\t', b';', b'|'];
+
+/// 在前若干行里找每行出现次数最一致的分隔符
+fn sniff_dialect(input: &str) -> (u8, u8) {
+    let lines = input
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .take(20)
+        .collect::<Vec<_>>();
+
+    let quote =
+        if !lines.iter().any(|l| l.contains('"')) && lines.iter().any(|l| quotes_fields(l, '\'')) {
+            b'\''
+        } else {
+            b'"'
+        };
+
+    let mut best = (b',', 0, 0);
+    for delimiter in DELIMITERS {
+        let counts = lines
+            .iter()
+            .map(|line| count_unquoted(line, delimiter, quote))
+            .collect::<Vec<_>>();
+
+        let mut mode = (0, 0);
+        for &count in &counts {
+            let freq = counts.iter().filter(|&&c| c == count).count();
+            if count > 0 && (freq, count) > mode {
+                mode = (freq, count);
+            }
+        }
+
+        if mode > (best.1, best.2) {
+            best = (delimiter, mode.0, mode.1);
+        }
+    }
+
+    (best.0, quote)
+}
+
+/// 引号出现在字段开头和结尾（紧挨分隔符或行首行尾）时才算作引号，`O'Brien` 中的撇号不算
+fn quotes_fields(line: &str, quote: char) -> bool {
+    let is_boundary = |c: Option<char>| match c {
+        None => true,
+        Some(c) => c.is_ascii() && DELIMITERS.contains(&(c as u8)),
+    };
+    let positions = line.match_indices(quote).map(|(i, _)| i);
+    let open = positions
+        .clone()
+        .find(|&i| is_boundary(line[..i].trim_end_matches(' ').chars().next_back()));
+    let close = positions
+        .rev()
+        .find(|&i| is_boundary(line[i + 1..].trim_start_matches(' ').chars().next()));
+    matches!((open, close), (Some(open), Some(close)) if open < close)
+}
+
+/// 重复的列名依次加上 `_2`、`_3`，避免转成 JSON 对象时覆盖前面的列
+fn dedupe_columns(columns: &mut [String]) {
+    let mut used = HashSet::new();
+    for column in columns.iter_mut() {
+        let base = column.clone();
+        let mut k = 2;
+        while !used.insert(column.clone()) {
+            *column = format!("{base}_{k}");
+            k += 1;
+        }
+    }
+}
+
+fn count_unquoted(line: &str, delimiter: u8, quote: u8) -> usize {
+    let mut quoted = false;
+    let mut count = 0;
+    for b in line.bytes() {
+        if b == quote {
+            quoted = !quoted;
+        } else if b == delimiter && !quoted {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// 与 Python csv.Sniffer.has_header 相同的投票：
+/// 数字列的表头不是数字、定长列的表头长度不同，都算作有表头
+fn sniff_header(records: &[Vec<String>]) -> bool {
+    let Some((header, rows)) = records.split_first() else {
+        return false;
+    };
+    if rows.is_empty() {
+        return false;
+    }
+
+    let mut votes = 0;
+    for (n, title) in header.iter().enumerate() {
+        let column = rows
+            .iter()
+            .take(20)
+            .filter_map(|r| r.get(n))
+            .collect::<Vec<_>>();
+        if column.is_empty() {
+            continue;
+        }
+
+        if column.iter().all(|v| v.trim().parse::<f64>().is_ok()) {
+            votes += if title.trim().parse::<f64>().is_ok() {
+                -1
+            } else {
+                1
+            };
+        } else if column.iter().all(|v| v.len() == column[0].len()) {
+            votes += if title.len() == column[0].len() {
+                -1
+            } else {
+                1
+            };
+        }
+    }
+
+    votes > 0
+}
+
+fn infer_value(field: &str) -> Value {
+    let trimmed = field.trim();
+    if trimmed.is_empty() {
+        return Value::Null;
+    }
+    match trimmed {
+        "true" | "TRUE" | "True" => return Value::Bool(true),
+        "false" | "FALSE" | "False" => return Value::Bool(false),
+        "null" | "NULL" => return Value::Null,
+        _ => {}
+    }
+    // 前导 0 的编号、电话等保持字符串
+    let leading_zero = trimmed.len() > 1 && trimmed.starts_with('0') && !trimmed.starts_with("0.");
+    if !leading_zero {
+        if let Ok(n) = trimmed.parse::<i64>() {
+            return Value::from(n);
+        }
+        if let Some(n) = trimmed
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+        {
+            return Value::Number(n);
+        }
+    }
+    Value::String(field.to_string())
+}
+
+fn markdown_table(columns: &[String], records: &[Vec<String>]) -> String {
+    let cell = |s: &str| s.replace('|', "\\|").replace('\n', "<br>");
+
+    let mut output = String::new();
+    output.push_str(&format!(
+        "| {} |\n",
+        columns
+            .iter()
+            .map(|c| cell(c))
+            .collect::<Vec<_>>()
+            .join(" | ")
+    ));
+    output.push_str(&format!(
+        "|{}|\n",
+        columns
+            .iter()
+            .map(|_| " --- ")
+            .collect::<Vec<_>>()
+            .join("|")
+    ));
+    for record in records {
+        let cells = (0..columns.len())
+            .map(|n| cell(record.get(n).map_or("", |s| s.as_str())))
+            .collect::<Vec<_>>();
+        output.push_str(&format!("| {} |\n", cells.join(" | ")));
+    }
+    output
+}
+
+fn text_table(columns: &[String], records: &[Vec<String>]) -> String {
+    let cell =
+        |record: &[String], n: usize| record.get(n).map_or("", |s| s.as_str()).replace('\n', " ");
+
+    let mut widths = columns.iter().map(|c| c.width()).collect::<Vec<_>>();
+    for record in records {
+        for (n, width) in widths.iter_mut().enumerate() {
+            *width = (*width).max(cell(record, n).width());
+        }
+    }
+
+    let line = |cells: Vec<String>| {
+        let padded = cells
+            .iter()
+            .zip(&widths)
+            .map(|(c, w)| format!("{c}{}", " ".repeat(w - c.width())))
+            .collect::<Vec<_>>();
+        format!("| {} |\n", padded.join(" | "))
+    };
+    let border = format!(
+        "+{}+\n",
+        widths
+            .iter()
+            .map(|w| "-".repeat(w + 2))
+            .collect::<Vec<_>>()
+            .join("+")
+    );
+
+    let mut output = border.clone();
+    output.push_str(&line(columns.to_vec()));
+    output.push_str(&border);
+    for record in records {
+        output.push_str(&line((0..columns.len()).map(|n| cell(record, n)).collect()));
+    }
+    output.push_str(&border);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn columns_keep_source_order() {
+        let table = csv_convert("zeta,alpha,mid\n1,2,3\n", None, Some(true), None, None).unwrap();
+        let rows = serde_json::from_str::<Vec<Map<String, Value>>>(&table.output).unwrap();
+        let keys = rows[0].keys().collect::<Vec<_>>();
+        assert_eq!(keys, ["zeta", "alpha", "mid"]);
+
+        let csv = json_to_csv(
+            r#"[{"zeta": 1, "alpha": {"b": 2, "a": 3}, "mid": 4}]"#,
+            None,
+        )
+        .unwrap();
+        assert_eq!(csv.lines().next(), Some("zeta,alpha.b,alpha.a,mid"));
+    }
+}
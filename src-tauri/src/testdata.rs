@@ -0,0 +1,647 @@
+use fake::faker::address::raw::{BuildingNumber, CityName, StateAbbr, StreetName};
+use fake::faker::company::raw::CompanyName;
+use fake::faker::internet::raw::{
+    DomainSuffix, FreeEmail, IPv4, IPv6, MACAddress, Password, UserAgent, Username,
+};
+use fake::faker::job::raw::Title;
+use fake::faker::lorem::raw::{Paragraph, Sentence, Word};
+use fake::faker::name::raw::{FirstName, LastName, Name};
+use fake::faker::phone_number::raw::PhoneNumber;
+use fake::locales::{EN, ZH_CN};
+use fake::Fake;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::random::Source;
+use crate::sql::sql_literal;
+
+/// 单次最多生成的记录数
+const MAX_RECORDS: usize = 100_000;
+
+/// 行政区划代码、省、市、区，用于中文地址和身份证号
+const ZH_REGIONS: [(&str, &str, &str, &str); 12] = [
+    ("110101", "北京市", "北京市", "东城区"),
+    ("110108", "北京市", "北京市", "海淀区"),
+    ("310101", "上海市", "上海市", "黄浦区"),
+    ("310115", "上海市", "上海市", "浦东新区"),
+    ("120101", "天津市", "天津市", "和平区"),
+    ("440106", "广东省", "广州市", "天河区"),
+    ("440305", "广东省", "深圳市", "南山区"),
+    ("330106", "浙江省", "杭州市", "西湖区"),
+    ("320102", "江苏省", "南京市", "玄武区"),
+    ("420106", "湖北省", "武汉市", "武昌区"),
+    ("510107", "四川省", "成都市", "武侯区"),
+    ("610113", "陕西省", "西安市", "雁塔区"),
+];
+const ZH_STREETS: [&str; 12] = [
+    "人民路",
+    "中山路",
+    "解放路",
+    "建设路",
+    "和平路",
+    "新华路",
+    "长江路",
+    "黄河路",
+    "文化路",
+    "学府路",
+    "科技路",
+    "滨江大道",
+];
+const ZH_COMPANY_WORDS: [&str; 10] = [
+    "华信", "恒通", "中科", "天成", "瑞达", "星辰", "博远", "联创", "东方", "鼎盛",
+];
+const ZH_INDUSTRIES: [&str; 6] = ["科技", "信息技术", "网络", "贸易", "物流", "咨询"];
+const ZH_MOBILE_PREFIXES: [&str; 16] = [
+    "130", "131", "132", "135", "136", "137", "138", "139", "150", "152", "158", "177", "180",
+    "186", "188", "199",
+];
+const ZH_EMAIL_DOMAINS: [&str; 4] = ["qq.com", "163.com", "126.com", "sina.com"];
+
+#[derive(Deserialize, Debug)]
+pub struct FakeField {
+    pub name: String,
+    /// 数据类型，部分类型可带参数如 `integer:1-100`，含 `{...}` 时按模板处理
+    pub kind: String,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct FakeOptions {
+    pub count: usize,
+    /// `en_US`、`zh_CN`
+    pub locale: String,
+    pub fields: Vec<FakeField>,
+    /// 单列模板，如 `{name} <{email}>`，指定时忽略 `fields`
+    pub template: Option<String>,
+    /// `json`、`csv`、`sql`、`text`
+    pub format: String,
+    /// SQL INSERT 的表名
+    pub table: String,
+}
+
+impl Default for FakeOptions {
+    fn default() -> Self {
+        FakeOptions {
+            count: 10,
+            locale: "en_US".to_string(),
+            fields: vec![],
+            template: None,
+            format: "json".to_string(),
+            table: "fake_data".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Locale {
+    EnUs,
+    ZhCn,
+}
+
+#[derive(Debug)]
+enum Kind {
+    Name,
+    FirstName,
+    LastName,
+    Username,
+    Email,
+    Phone,
+    Address,
+    Street,
+    City,
+    State,
+    Zip,
+    Company,
+    Job,
+    Ipv4,
+    Ipv6,
+    Mac,
+    Domain,
+    Url,
+    Uuid,
+    CreditCard,
+    IdCard,
+    Ssn,
+    Password,
+    UserAgent,
+    Word,
+    Sentence,
+    Paragraph,
+    Boolean,
+    /// 从 1 开始的序号
+    Sequence,
+    Integer(i64, i64),
+    Float(f64, f64),
+    /// 起止年份
+    Date(i32, i32),
+    DateTime(i32, i32),
+    Template(Vec<Segment>),
+}
+
+#[derive(Debug)]
+enum Segment {
+    Literal(String),
+    Field(Kind),
+}
+
+/// 按字段列表或模板生成测试数据，输出 JSON、CSV、SQL INSERT 或纯文本
+#[tauri::command]
+pub fn gen_fake_data(options: FakeOptions, seed: Option<u64>) -> Result<String, String> {
+    let locale = match options.locale.as_str() {
+        "" | "en_US" | "en" => Locale::EnUs,
+        "zh_CN" | "zh" => Locale::ZhCn,
+        other => return Err(format!("unsupported locale: {other}")),
+    };
+    if options.count > MAX_RECORDS {
+        return Err(format!("count must not exceed {MAX_RECORDS}"));
+    }
+
+    let fields = match &options.template {
+        Some(template) => vec![(
+            "value".to_string(),
+            Kind::Template(parse_template(template)?),
+        )],
+        None => {
+            if options.fields.is_empty() {
+                return Err("at least one field or a template is required".to_string());
+            }
+            options
+                .fields
+                .iter()
+                .map(|f| {
+                    let kind = parse_kind(&f.kind).map_err(|e| format!("{}: {e}", f.name))?;
+                    Ok((f.name.clone(), kind))
+                })
+                .collect::<Result<Vec<_>, String>>()?
+        }
+    };
+
+    let mut rng = Source::new(seed);
+    let records = (0..options.count)
+        .map(|i| {
+            fields
+                .iter()
+                .map(|(_, kind)| generate(kind, locale, i + 1, &mut rng))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let columns = fields
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>();
+
+    match options.format.as_str() {
+        "json" => Ok(to_json(&columns, &records)),
+        "csv" => to_csv(&columns, &records),
+        "sql" => Ok(to_sql(&options.table, &columns, &records)),
+        "text" => Ok(records
+            .iter()
+            .map(|r| r.iter().map(plain).collect::<Vec<_>>().join("\t"))
+            .collect::<Vec<_>>()
+            .join("\n")),
+        other => Err(format!("unknown output format: {other}")),
+    }
+}
+
+fn parse_kind(kind: &str) -> Result<Kind, String> {
+    if kind.contains('{') {
+        return Ok(Kind::Template(parse_template(kind)?));
+    }
+
+    let (name, args) = match kind.split_once(':') {
+        Some((name, args)) => (name.trim(), Some(args.trim())),
+        None => (kind.trim(), None),
+    };
+    // `min-max`，负数写作 `-10--1`，指数中的 `-`（`1e-5`）不作分隔
+    let range = |default: (&str, &str)| -> Result<(String, String), String> {
+        let Some(args) = args else {
+            return Ok((default.0.to_string(), default.1.to_string()));
+        };
+        let split = args
+            .char_indices()
+            .skip(1)
+            .find(|&(i, c)| c == '-' && !args[..i].ends_with(['e', 'E']))
+            .map(|(i, _)| i)
+            .ok_or_else(|| format!("range must look like min-max: {args}"))?;
+        Ok((args[..split].to_string(), args[split + 1..].to_string()))
+    };
+    let parse_int = |s: &str| {
+        s.trim()
+            .parse::<i64>()
+            .map_err(|_| format!("invalid integer in {kind}: {s}"))
+    };
+    let parse_float = |s: &str| match s.trim().parse::<f64>() {
+        Ok(n) if n.is_finite() => Ok(n),
+        _ => Err(format!("invalid number in {kind}: {s}")),
+    };
+
+    let kind = match name {
+        "name" => Kind::Name,
+        "first_name" => Kind::FirstName,
+        "last_name" => Kind::LastName,
+        "username" => Kind::Username,
+        "email" => Kind::Email,
+        "phone" => Kind::Phone,
+        "address" => Kind::Address,
+        "street" => Kind::Street,
+        "city" => Kind::City,
+        "state" | "province" => Kind::State,
+        "zip" | "postcode" => Kind::Zip,
+        "company" => Kind::Company,
+        "job" => Kind::Job,
+        "ipv4" => Kind::Ipv4,
+        "ipv6" => Kind::Ipv6,
+        "mac" => Kind::Mac,
+        "domain" => Kind::Domain,
+        "url" => Kind::Url,
+        "uuid" => Kind::Uuid,
+        "credit_card" => Kind::CreditCard,
+        "id_card" => Kind::IdCard,
+        "ssn" => Kind::Ssn,
+        "password" => Kind::Password,
+        "user_agent" => Kind::UserAgent,
+        "word" => Kind::Word,
+        "sentence" => Kind::Sentence,
+        "paragraph" => Kind::Paragraph,
+        "boolean" => Kind::Boolean,
+        "sequence" => Kind::Sequence,
+        "integer" => {
+            let (min, max) = range(("0", "1000"))?;
+            let (min, max) = (parse_int(&min)?, parse_int(&max)?);
+            if min > max {
+                return Err(format!("empty range in {kind}"));
+            }
+            Kind::Integer(min, max)
+        }
+        "float" => {
+            let (min, max) = range(("0", "1000"))?;
+            let (min, max) = (parse_float(&min)?, parse_float(&max)?);
+            if min > max {
+                return Err(format!("empty range in {kind}"));
+            }
+            // gen_range 要求跨度本身也是有限值
+            if !(max - min).is_finite() {
+                return Err(format!("range is too wide in {kind}"));
+            }
+            Kind::Float(min, max)
+        }
+        "date" | "datetime" => {
+            let (from, to) = range(("1970", "2030"))?;
+            let (from, to) = (parse_int(&from)?, parse_int(&to)?);
+            if from > to || from < 1 || to > 9999 {
+                return Err(format!("years must be between 1 and 9999 in {kind}"));
+            }
+            if name == "date" {
+                Kind::Date(from as i32, to as i32)
+            } else {
+                Kind::DateTime(from as i32, to as i32)
+            }
+        }
+        other => return Err(format!("unknown field kind: {other}")),
+    };
+
+    if args.is_some()
+        && !matches!(
+            kind,
+            Kind::Integer(..) | Kind::Float(..) | Kind::Date(..) | Kind::DateTime(..)
+        )
+    {
+        return Err(format!("{name} does not take arguments"));
+    }
+    Ok(kind)
+}
+
+/// `{kind}` 为占位符，`{{`、`}}` 为花括号本身
+fn parse_template(template: &str) -> Result<Vec<Segment>, String> {
+    let mut segments = vec![];
+    let mut literal = String::new();
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut placeholder = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some('{') => {
+                            return Err(format!("nested placeholder in template: {template}"))
+                        }
+                        Some(c) => placeholder.push(c),
+                        None => {
+                            return Err(format!("unclosed placeholder in template: {template}"))
+                        }
+                    }
+                }
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(Segment::Field(parse_kind(&placeholder)?));
+            }
+            '}' => return Err(format!("unmatched '}}' in template: {template}")),
+            c => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+    Ok(segments)
+}
+
+fn generate(kind: &Kind, locale: Locale, index: usize, rng: &mut Source) -> Value {
+    let zh = locale == Locale::ZhCn;
+    let text = |s: String| Value::String(s);
+    match kind {
+        Kind::Name if zh => text(Name(ZH_CN).fake_with_rng(rng)),
+        Kind::Name => text(Name(EN).fake_with_rng(rng)),
+        Kind::FirstName if zh => text(FirstName(ZH_CN).fake_with_rng(rng)),
+        Kind::FirstName => text(FirstName(EN).fake_with_rng(rng)),
+        Kind::LastName if zh => text(LastName(ZH_CN).fake_with_rng(rng)),
+        Kind::LastName => text(LastName(EN).fake_with_rng(rng)),
+        Kind::Username => text(Username(EN).fake_with_rng(rng)),
+        Kind::Email if zh => {
+            let user: String = Username(EN).fake_with_rng(rng);
+            text(format!("{user}@{}", pick(&ZH_EMAIL_DOMAINS, rng)))
+        }
+        Kind::Email => text(FreeEmail(EN).fake_with_rng(rng)),
+        Kind::Phone if zh => text(format!(
+            "{}{:08}",
+            pick(&ZH_MOBILE_PREFIXES, rng),
+            rng.gen_range(0..100_000_000)
+        )),
+        Kind::Phone => text(PhoneNumber(EN).fake_with_rng(rng)),
+        Kind::Address if zh => {
+            let (_, province, city, district) = pick(&ZH_REGIONS, rng);
+            let province = if province == city { "" } else { province };
+            text(format!(
+                "{province}{city}{district}{}{}号",
+                pick(&ZH_STREETS, rng),
+                rng.gen_range(1..=999)
+            ))
+        }
+        Kind::Address => {
+            let number: String = BuildingNumber(EN).fake_with_rng(rng);
+            let street: String = StreetName(EN).fake_with_rng(rng);
+            let city: String = CityName(EN).fake_with_rng(rng);
+            let state: String = StateAbbr(EN).fake_with_rng(rng);
+            let zip = us_zip(rng);
+            text(format!("{number} {street}, {city}, {state} {zip}"))
+        }
+        Kind::Street if zh => text(format!(
+            "{}{}号",
+            pick(&ZH_STREETS, rng),
+            rng.gen_range(1..=999)
+        )),
+        Kind::Street => {
+            let number: String = BuildingNumber(EN).fake_with_rng(rng);
+            let street: String = StreetName(EN).fake_with_rng(rng);
+            text(format!("{number} {street}"))
+        }
+        Kind::City if zh => text(pick(&ZH_REGIONS, rng).2.to_string()),
+        Kind::City => text(CityName(EN).fake_with_rng(rng)),
+        Kind::State if zh => text(pick(&ZH_REGIONS, rng).1.to_string()),
+        Kind::State => text(StateAbbr(EN).fake_with_rng(rng)),
+        Kind::Zip if zh => text(format!("{:06}", rng.gen_range(100_000..900_000))),
+        Kind::Zip => text(us_zip(rng)),
+        Kind::Company if zh => text(format!(
+            "{}{}{}有限公司",
+            pick(&ZH_REGIONS, rng).2.trim_end_matches('市'),
+            pick(&ZH_COMPANY_WORDS, rng),
+            pick(&ZH_INDUSTRIES, rng)
+        )),
+        Kind::Company => text(CompanyName(EN).fake_with_rng(rng)),
+        Kind::Job if zh => text(Title(ZH_CN).fake_with_rng(rng)),
+        Kind::Job => text(Title(EN).fake_with_rng(rng)),
+        Kind::Ipv4 => text(IPv4(EN).fake_with_rng(rng)),
+        Kind::Ipv6 => text(IPv6(EN).fake_with_rng(rng)),
+        Kind::Mac => text(MACAddress(EN).fake_with_rng(rng)),
+        Kind::Domain => text(domain(rng)),
+        Kind::Url => {
+            let domain = domain(rng);
+            let path: String = Word(EN).fake_with_rng(rng);
+            text(format!("https://{domain}/{path}"))
+        }
+        Kind::Uuid => text(
+            uuid::Builder::from_random_bytes(rng.gen())
+                .into_uuid()
+                .to_string(),
+        ),
+        Kind::CreditCard => text(credit_card(locale, rng)),
+        Kind::IdCard => text(id_card(rng)),
+        Kind::Ssn => text(format!(
+            "{:03}-{:02}-{:04}",
+            // 000、666、9xx 不会分配
+            [rng.gen_range(1..666), rng.gen_range(667..900)][rng.gen_range(0..2)],
+            rng.gen_range(1..100),
+            rng.gen_range(1..10_000)
+        )),
+        Kind::Password => text(Password(EN, 10..17).fake_with_rng(rng)),
+        Kind::UserAgent => text(UserAgent(EN).fake_with_rng(rng)),
+        Kind::Word => text(Word(EN).fake_with_rng(rng)),
+        Kind::Sentence => text(Sentence(EN, 4..12).fake_with_rng(rng)),
+        Kind::Paragraph => text(Paragraph(EN, 2..5).fake_with_rng(rng)),
+        Kind::Boolean => Value::Bool(rng.gen()),
+        Kind::Sequence => Value::from(index),
+        Kind::Integer(min, max) => Value::from(rng.gen_range(*min..=*max)),
+        Kind::Float(min, max) => {
+            let value = if min == max {
+                *min
+            } else {
+                rng.gen_range(*min..*max)
+            };
+            // 接近 f64 上限时保留两位小数会溢出
+            let rounded = (value * 100.0).round() / 100.0;
+            Value::from(if rounded.is_finite() { rounded } else { value })
+        }
+        Kind::Date(from, to) => {
+            let (y, m, d) = date(*from, *to, rng);
+            text(format!("{y:04}-{m:02}-{d:02}"))
+        }
+        Kind::DateTime(from, to) => {
+            let (y, m, d) = date(*from, *to, rng);
+            text(format!(
+                "{y:04}-{m:02}-{d:02} {:02}:{:02}:{:02}",
+                rng.gen_range(0..24),
+                rng.gen_range(0..60),
+                rng.gen_range(0..60)
+            ))
+        }
+        Kind::Template(segments) => text(
+            segments
+                .iter()
+                .map(|segment| match segment {
+                    Segment::Literal(s) => s.clone(),
+                    Segment::Field(kind) => plain(&generate(kind, locale, index, rng)),
+                })
+                .collect(),
+        ),
+    }
+}
+
+fn pick<T: Copy>(items: &[T], rng: &mut Source) -> T {
+    *items.choose(rng).expect("list is not empty")
+}
+
+fn us_zip(rng: &mut Source) -> String {
+    format!("{:05}", rng.gen_range(501..99_951))
+}
+
+fn domain(rng: &mut Source) -> String {
+    let word: String = Word(EN).fake_with_rng(rng);
+    let suffix: String = DomainSuffix(EN).fake_with_rng(rng);
+    format!("{}.{suffix}", word.to_lowercase())
+}
+
+fn date(from: i32, to: i32, rng: &mut Source) -> (i32, u32, u32) {
+    let year = rng.gen_range(from..=to);
+    let month = rng.gen_range(1..=12);
+    let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+    let days = match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    };
+    (year, month, rng.gen_range(1..=days))
+}
+
+/// 按卡组织的号段和长度生成，最后一位为 Luhn 校验位
+fn credit_card(locale: Locale, rng: &mut Source) -> String {
+    let mut issuers = vec![
+        ("4", 16),
+        ("51", 16),
+        ("55", 16),
+        ("2221", 16),
+        ("34", 15),
+        ("37", 15),
+        ("6011", 16),
+    ];
+    if locale == Locale::ZhCn {
+        // 银联
+        issuers = vec![("62", 16), ("62", 19), ("4", 16), ("51", 16)];
+    }
+    let (prefix, len) = pick(&issuers, rng);
+    let mut digits = prefix
+        .bytes()
+        .map(|b| u32::from(b - b'0'))
+        .collect::<Vec<_>>();
+    while digits.len() < len - 1 {
+        digits.push(rng.gen_range(0..10));
+    }
+
+    let sum = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| match i % 2 {
+            0 if d * 2 > 9 => d * 2 - 9,
+            0 => d * 2,
+            _ => d,
+        })
+        .sum::<u32>();
+    digits.push((10 - sum % 10) % 10);
+    digits
+        .iter()
+        .map(|d| char::from_digit(*d, 10).expect("digit"))
+        .collect()
+}
+
+/// GB 11643 身份证号：6 位地区码、8 位出生日期、3 位顺序码和 1 位校验码
+fn id_card(rng: &mut Source) -> String {
+    const WEIGHTS: [u32; 17] = [7, 9, 10, 5, 8, 4, 2, 1, 6, 3, 7, 9, 10, 5, 8, 4, 2];
+    const CHECK: &[u8] = b"10X98765432";
+
+    let (code, ..) = pick(&ZH_REGIONS, rng);
+    let (y, m, d) = date(1950, 2005, rng);
+    let body = format!("{code}{y:04}{m:02}{d:02}{:03}", rng.gen_range(1..1000));
+    let sum = body
+        .bytes()
+        .zip(WEIGHTS)
+        .map(|(b, w)| u32::from(b - b'0') * w)
+        .sum::<u32>();
+    format!("{body}{}", CHECK[(sum % 11) as usize] as char)
+}
+
+fn plain(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// 手工拼接以保持字段顺序
+fn to_json(columns: &[&str], records: &[Vec<Value>]) -> String {
+    let objects = records
+        .iter()
+        .map(|record| {
+            let fields = columns
+                .iter()
+                .zip(record)
+                .map(|(column, value)| format!("    {}: {value}", Value::from(*column)))
+                .collect::<Vec<_>>();
+            format!("  {{\n{}\n  }}", fields.join(",\n"))
+        })
+        .collect::<Vec<_>>();
+    if objects.is_empty() {
+        return "[]".to_string();
+    }
+    format!("[\n{}\n]", objects.join(",\n"))
+}
+
+fn to_csv(columns: &[&str], records: &[Vec<Value>]) -> Result<String, String> {
+    let mut writer = csv::Writer::from_writer(vec![]);
+    writer.write_record(columns).map_err(|e| e.to_string())?;
+    for record in records {
+        writer
+            .write_record(record.iter().map(plain))
+            .map_err(|e| e.to_string())?;
+    }
+    let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+    Ok(String::from_utf8_lossy(&bytes).to_string())
+}
+
+fn to_sql(table: &str, columns: &[&str], records: &[Vec<Value>]) -> String {
+    let columns = columns
+        .iter()
+        .map(|c| identifier(c))
+        .collect::<Vec<_>>()
+        .join(", ");
+    records
+        .iter()
+        .map(|record| {
+            let values = record
+                .iter()
+                .map(sql_literal)
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!(
+                "INSERT INTO {} ({columns}) VALUES ({values});",
+                identifier(table)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 不是普通标识符时加双引号
+fn identifier(name: &str) -> String {
+    let plain = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if plain {
+        name.to_string()
+    } else {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+}
@@ -0,0 +1,236 @@
+use base64::{engine::general_purpose, Engine as _};
+use rand::seq::SliceRandom;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use crate::codec::base32_encode;
+use crate::random::Source;
+
+/// 单次最多生成的随机字节数
+const MAX_BYTES: usize = 1024 * 1024;
+
+/// 单次最多生成的个数
+const MAX_COUNT: usize = 10_000;
+
+/// token 主体的最大长度
+const MAX_TOKEN_LENGTH: usize = 1024;
+
+/// 从操作系统的 CSPRNG 读取随机字节，编码为 `hex`、`base64`、`base64url`（无填充）或 `base32`
+///
+/// 指定 `seed` 时改用 ChaCha20，只应用于测试数据。
+#[tauri::command]
+pub fn gen_random_bytes(
+    count: usize,
+    length: usize,
+    encoding: &str,
+    seed: Option<u64>,
+) -> Result<Vec<String>, String> {
+    if length == 0 || length > MAX_BYTES {
+        return Err(format!("length must be between 1 and {MAX_BYTES} bytes"));
+    }
+    if count > MAX_COUNT {
+        return Err(format!("count must not exceed {MAX_COUNT}"));
+    }
+    if count * length > MAX_BYTES {
+        return Err(format!("count * length must not exceed {MAX_BYTES} bytes"));
+    }
+    if !matches!(encoding, "hex" | "base64" | "base64url" | "base32") {
+        return Err(format!("unknown encoding: {encoding}"));
+    }
+
+    let mut rng = Source::new(seed);
+    let mut bytes = vec![0u8; length];
+    (0..count)
+        .map(|_| {
+            rng.try_fill_bytes(&mut bytes).map_err(|e| e.to_string())?;
+            Ok(match encoding {
+                "hex" => bytes.iter().map(|b| format!("{b:02x}")).collect(),
+                "base64" => general_purpose::STANDARD.encode(&bytes),
+                "base64url" => general_purpose::URL_SAFE_NO_PAD.encode(&bytes),
+                _ => base32_encode(&bytes, true),
+            })
+        })
+        .collect()
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct TokenTemplate {
+    /// 固定前缀，如 `ghp_`
+    pub prefix: String,
+    /// 随机部分的字符数
+    pub length: usize,
+    /// `base62`、`base32`、`hex`、`digits`
+    pub alphabet: String,
+    /// `crc32`：随机部分的 CRC32 按字母表编码后追加，与 GitHub token 相同；
+    /// `luhn`：追加一位 Luhn mod N 校验字符；`none`
+    pub checksum: String,
+}
+
+impl Default for TokenTemplate {
+    fn default() -> Self {
+        TokenTemplate {
+            prefix: String::new(),
+            length: 30,
+            alphabet: "base62".to_string(),
+            checksum: "crc32".to_string(),
+        }
+    }
+}
+
+impl TokenTemplate {
+    fn alphabet(&self) -> Result<&'static [u8], String> {
+        match self.alphabet.as_str() {
+            "base62" => Ok(b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz"),
+            "base32" => Ok(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567"),
+            "hex" => Ok(b"0123456789abcdef"),
+            "digits" => Ok(b"0123456789"),
+            other => Err(format!("unknown token alphabet: {other}")),
+        }
+    }
+
+    /// 校验部分的字符数
+    fn checksum_len(&self, alphabet: &[u8]) -> Result<usize, String> {
+        match self.checksum.as_str() {
+            "none" => Ok(0),
+            "luhn" => Ok(1),
+            "crc32" => {
+                // 能容纳 32 位的最少位数
+                let mut width = 0;
+                let mut capacity = 1u64;
+                while capacity <= u64::from(u32::MAX) {
+                    capacity *= alphabet.len() as u64;
+                    width += 1;
+                }
+                Ok(width)
+            }
+            other => Err(format!("unknown checksum: {other}")),
+        }
+    }
+
+    fn checksum(&self, alphabet: &[u8], body: &str) -> Result<String, String> {
+        let width = self.checksum_len(alphabet)?;
+        Ok(match self.checksum.as_str() {
+            "crc32" => encode_fixed(crc32fast::hash(body.as_bytes()), alphabet, width),
+            "luhn" => luhn_mod_n(alphabet, body)?.to_string(),
+            _ => String::new(),
+        })
+    }
+}
+
+/// 按模板生成带前缀和校验的 token
+#[tauri::command]
+pub fn gen_tokens(
+    count: usize,
+    template: Option<TokenTemplate>,
+    seed: Option<u64>,
+) -> Result<Vec<String>, String> {
+    let template = template.unwrap_or_default();
+    let alphabet = template.alphabet()?;
+    template.checksum_len(alphabet)?;
+    if template.length == 0 || template.length > MAX_TOKEN_LENGTH {
+        return Err(format!(
+            "token length must be between 1 and {MAX_TOKEN_LENGTH}"
+        ));
+    }
+    if count > MAX_COUNT {
+        return Err(format!("count must not exceed {MAX_COUNT}"));
+    }
+
+    let mut rng = Source::new(seed);
+    (0..count)
+        .map(|_| {
+            let body = (0..template.length)
+                .map(|_| *alphabet.choose(&mut rng).expect("alphabet is not empty") as char)
+                .collect::<String>();
+            let checksum = template.checksum(alphabet, &body)?;
+            Ok(format!("{}{body}{checksum}", template.prefix))
+        })
+        .collect()
+}
+
+#[derive(Serialize, Debug)]
+pub struct TokenCheck {
+    pub valid: bool,
+    /// 不合法的原因
+    pub reason: Option<String>,
+    pub expected_checksum: Option<String>,
+    pub actual_checksum: Option<String>,
+}
+
+/// 按模板检查 token 的前缀、长度、字符和校验位
+#[tauri::command]
+pub fn validate_token(token: &str, template: Option<TokenTemplate>) -> Result<TokenCheck, String> {
+    let template = template.unwrap_or_default();
+    let alphabet = template.alphabet()?;
+    let checksum_len = template.checksum_len(alphabet)?;
+
+    let invalid = |reason: String| TokenCheck {
+        valid: false,
+        reason: Some(reason),
+        expected_checksum: None,
+        actual_checksum: None,
+    };
+
+    let Some(rest) = token.trim().strip_prefix(&template.prefix) else {
+        return Ok(invalid(format!(
+            "token does not start with {:?}",
+            template.prefix
+        )));
+    };
+    if let Some(c) = rest
+        .chars()
+        .find(|c| !c.is_ascii() || !alphabet.contains(&(*c as u8)))
+    {
+        return Ok(invalid(format!(
+            "{c:?} is not in the {} alphabet",
+            template.alphabet
+        )));
+    }
+    if rest.len() != template.length + checksum_len {
+        return Ok(invalid(format!(
+            "expected {} characters after the prefix, found {}",
+            template.length + checksum_len,
+            rest.len()
+        )));
+    }
+
+    let (body, actual) = rest.split_at(template.length);
+    let expected = template.checksum(alphabet, body)?;
+    let valid = expected == actual;
+    Ok(TokenCheck {
+        valid,
+        reason: (!valid).then(|| "checksum mismatch".to_string()),
+        expected_checksum: Some(expected),
+        actual_checksum: Some(actual.to_string()),
+    })
+}
+
+/// 按字母表编码为定长字符串，左侧以第一个字符补齐
+fn encode_fixed(mut value: u32, alphabet: &[u8], width: usize) -> String {
+    let base = alphabet.len() as u32;
+    let mut digits = vec![alphabet[0]; width];
+    for d in digits.iter_mut().rev() {
+        *d = alphabet[(value % base) as usize];
+        value /= base;
+    }
+    digits.into_iter().map(char::from).collect()
+}
+
+/// Luhn mod N，字母表为数字时即标准 Luhn 校验位
+fn luhn_mod_n(alphabet: &[u8], body: &str) -> Result<char, String> {
+    let n = alphabet.len();
+    let mut sum = 0;
+    for (i, c) in body.bytes().rev().enumerate() {
+        let mut value = alphabet
+            .iter()
+            .position(|&a| a == c)
+            .ok_or_else(|| format!("{:?} is not in the alphabet", c as char))?;
+        if i % 2 == 0 {
+            value *= 2;
+            value = value / n + value % n;
+        }
+        sum += value;
+    }
+    Ok(alphabet[(n - sum % n) % n] as char)
+}
@@ -0,0 +1,229 @@
+use crate::regex::{build_regex, RegexFlags};
+
+/// 把通配模式转换为等价的正则，结果可以直接交给 `regex_*` 命令测试
+///
+/// `syntax` 取 `glob`（`*`、`?`、`**`、`[...]`、`{a,b}`，`*` 不跨越 `/`，路径规则同 `.gitignore`）、
+/// `like`（SQL `%`、`_`）、`similar`（SQL SIMILAR TO）、`wildcard`（简单的 `*`、`?`）。
+/// `escape` 为 LIKE/SIMILAR TO 的转义字符，LIKE 默认无转义，SIMILAR TO 默认 `\`。
+#[tauri::command]
+pub fn wildcard_to_regex(
+    pattern: &str,
+    syntax: &str,
+    escape: Option<String>,
+    case_insensitive: Option<bool>,
+) -> Result<String, String> {
+    let escape = match escape.as_deref() {
+        None | Some("") => None,
+        Some(e) if e.chars().count() == 1 => e.chars().next(),
+        Some(e) => return Err(format!("escape must be a single character: {e}")),
+    };
+
+    let chars = pattern.chars().collect::<Vec<_>>();
+    let body = match syntax {
+        "glob" => {
+            // 同 .gitignore：除结尾外不含 `/` 的模式匹配任意目录下的同名路径，开头的 `/` 锚定到根目录
+            let floating = !chars[..chars.len().saturating_sub(1)].contains(&'/');
+            let (chars, prefix) = match chars.split_first() {
+                Some(('/', rest)) => (rest, ""),
+                _ if floating => (&chars[..], "(?:.*/)?"),
+                _ => (&chars[..], ""),
+            };
+            let mut i = 0;
+            format!("{prefix}{}", glob(chars, &mut i, 0)?)
+        }
+        "like" => like(&chars, escape)?,
+        "similar" => similar(&chars, escape.or(Some('\\')))?,
+        "wildcard" => chars
+            .iter()
+            .map(|c| match c {
+                '*' => ".*".to_string(),
+                '?' => ".".to_string(),
+                c => regex::escape(&c.to_string()),
+            })
+            .collect(),
+        other => return Err(format!("unknown pattern syntax: {other}")),
+    };
+
+    // LIKE/SIMILAR TO 的 `_`、`%` 和简单通配的 `*`、`?` 可以匹配换行
+    let mut flags = String::new();
+    if case_insensitive.unwrap_or(false) {
+        flags.push('i');
+    }
+    if matches!(syntax, "like" | "similar" | "wildcard") {
+        flags.push('s');
+    }
+    let regex = if flags.is_empty() {
+        format!("^{body}$")
+    } else {
+        format!("(?{flags})^{body}$")
+    };
+
+    build_regex(&regex, &RegexFlags::default())?;
+    Ok(regex)
+}
+
+/// 解析到 `end` 或者（在花括号内时）`,`、`}` 为止
+fn glob(chars: &[char], i: &mut usize, depth: usize) -> Result<String, String> {
+    let mut output = String::new();
+    while *i < chars.len() {
+        let c = chars[*i];
+        match c {
+            ',' | '}' if depth > 0 => return Ok(output),
+            '*' if chars.get(*i + 1) == Some(&'*') => {
+                let segment_start = *i == 0 || chars[*i - 1] == '/';
+                let next = chars.get(*i + 2);
+                if segment_start && next == Some(&'/') {
+                    // `**/` 匹配零个或多个目录
+                    output.push_str("(?:.*/)?");
+                    *i += 3;
+                } else if segment_start && next.is_none() {
+                    output.push_str(".*");
+                    *i += 2;
+                } else {
+                    output.push_str("[^/]*");
+                    *i += 2;
+                }
+                continue;
+            }
+            '*' => output.push_str("[^/]*"),
+            '?' => output.push_str("[^/]"),
+            '[' => {
+                *i += 1;
+                output.push_str(&glob_class(chars, i)?);
+                continue;
+            }
+            '{' => {
+                let start = *i;
+                let mut alternatives = vec![];
+                loop {
+                    *i += 1;
+                    alternatives.push(glob(chars, i, depth + 1)?);
+                    match chars.get(*i) {
+                        Some(',') => continue,
+                        Some('}') => break,
+                        _ => return Err(format!("unclosed brace at position {start}")),
+                    }
+                }
+                output.push_str(&format!("(?:{})", alternatives.join("|")));
+            }
+            '\\' if *i + 1 < chars.len() => {
+                *i += 1;
+                output.push_str(&regex::escape(&chars[*i].to_string()));
+            }
+            c => output.push_str(&regex::escape(&c.to_string())),
+        }
+        *i += 1;
+    }
+    Ok(output)
+}
+
+/// `[!a-z]` 或 `[^a-z]`，开头的 `]` 是普通字符
+fn glob_class(chars: &[char], i: &mut usize) -> Result<String, String> {
+    let start = *i - 1;
+    let mut output = String::from("[");
+    if matches!(chars.get(*i), Some('!' | '^')) {
+        output.push('^');
+        *i += 1;
+    }
+
+    let first = *i;
+    loop {
+        let Some(&c) = chars.get(*i) else {
+            return Err(format!("unclosed character class at position {start}"));
+        };
+        *i += 1;
+        match c {
+            ']' if *i - 1 > first => break,
+            '\\' => {
+                let Some(&next) = chars.get(*i) else {
+                    return Err(format!("unclosed character class at position {start}"));
+                };
+                *i += 1;
+                output.push_str(&class_char(next));
+            }
+            '-' => output.push('-'),
+            c => output.push_str(&class_char(c)),
+        }
+    }
+    output.push(']');
+    Ok(output)
+}
+
+fn class_char(c: char) -> String {
+    match c {
+        '[' | ']' | '\\' | '^' | '&' | '~' | '-' => format!("\\{c}"),
+        c => c.to_string(),
+    }
+}
+
+fn like(chars: &[char], escape: Option<char>) -> Result<String, String> {
+    let mut output = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if Some(c) == escape {
+            let Some(&next) = chars.get(i + 1) else {
+                return Err("LIKE pattern must not end with the escape character".to_string());
+            };
+            output.push_str(&regex::escape(&next.to_string()));
+            i += 2;
+            continue;
+        }
+        match c {
+            '%' => output.push_str(".*"),
+            '_' => output.push('.'),
+            c => output.push_str(&regex::escape(&c.to_string())),
+        }
+        i += 1;
+    }
+    Ok(output)
+}
+
+/// SIMILAR TO 的 `|`、`*`、`+`、`?`、`{m,n}`、`()`、`[...]` 与正则相同，`.` 是普通字符
+fn similar(chars: &[char], escape: Option<char>) -> Result<String, String> {
+    let mut output = String::new();
+    let mut in_class = false;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if Some(c) == escape {
+            let Some(&next) = chars.get(i + 1) else {
+                return Err("SIMILAR TO pattern must not end with the escape character".to_string());
+            };
+            output.push_str(&regex::escape(&next.to_string()));
+            i += 2;
+            continue;
+        }
+        if in_class {
+            match c {
+                ']' => {
+                    in_class = false;
+                    output.push(c);
+                }
+                // regex 把类中的 `&&`、`--`、`~~` 当作集合运算，`[` 当作嵌套类
+                '&' | '~' | '[' => output.push_str(&format!("\\{c}")),
+                '-' if chars[i - 1] == '-' || chars.get(i + 1) == Some(&'-') => {
+                    output.push_str("\\-")
+                }
+                c => output.push(c),
+            }
+        } else {
+            match c {
+                '%' => output.push_str(".*"),
+                '_' => output.push('.'),
+                '[' => {
+                    in_class = true;
+                    output.push(c);
+                }
+                '|' | '*' | '+' | '?' | '{' | '}' | '(' | ')' | ',' => output.push(c),
+                c if c.is_ascii_digit() => output.push(c),
+                c => output.push_str(&regex::escape(&c.to_string())),
+            }
+        }
+        i += 1;
+    }
+    if in_class {
+        return Err("unclosed character class in SIMILAR TO pattern".to_string());
+    }
+    Ok(output)
+}
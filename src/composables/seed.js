@@ -0,0 +1,28 @@
+import { computed, ref } from "vue";
+
+// 种子为空时使用系统随机，否则结果可复现
+export function useSeed() {
+    const xSeed = ref("");
+    const xSeedError = computed(() => {
+        let value = String(xSeed.value).trim();
+        if (value == "" || (/^\d+$/.test(value) && Number.isSafeInteger(Number(value)))) {
+            return "";
+        }
+        return "种子必须是 0 到 2^53 - 1 之间的整数";
+    });
+    const xSeedInUse = computed(() => String(xSeed.value).trim() != "" && xSeedError.value == "");
+
+    // 种子非法时抛出错误，不悄悄退回系统随机
+    function seed() {
+        let value = String(xSeed.value).trim();
+        if (value == "") {
+            return null;
+        }
+        if (xSeedError.value != "") {
+            throw xSeedError.value;
+        }
+        return Number(value);
+    }
+
+    return { xSeed, xSeedError, xSeedInUse, seed };
+}